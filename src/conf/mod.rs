@@ -1,10 +1,68 @@
 pub use self::parse::parse_cmdopts;
+use crate::util::OptMapAsciiMode;
+use crate::util::OptTableLang;
 use crate::util::OptUcXParam;
 pub use parse::CmdOptConf;
 
 mod parse;
 
 impl CmdOptConf {
+    pub fn is_map_ascii(&self) -> bool {
+        self.opt_map_ascii != OptMapAsciiMode::Void
+    }
+    pub fn table_lang(&self) -> Option<OptTableLang> {
+        if self.opt_emit_table.is_some() {
+            self.opt_emit_table
+        } else if self.is_map_ascii() && self.is_opt_uc_x_map_ascii_rust_src() {
+            Some(OptTableLang::Rust)
+        } else {
+            None
+        }
+    }
+    pub fn table_name(&self) -> &str {
+        if self.opt_table_name.is_empty() {
+            "ASCII_STOCHAS"
+        } else {
+            self.opt_table_name.as_str()
+        }
+    }
+    pub fn is_fields(&self) -> bool {
+        self.flg_fields || self.opt_numeric_field.is_some()
+    }
+    pub fn field_delimiter(&self) -> char {
+        if self.flg_tab {
+            '\t'
+        } else {
+            self.opt_delimiter.chars().next().unwrap_or(',')
+        }
+    }
+    pub fn map_width(&self) -> usize {
+        if let Some(width) = self.opt_map_width {
+            return width;
+        }
+        match std::env::var("COLUMNS") {
+            Ok(s) => s.parse::<usize>().unwrap_or(80),
+            Err(_) => 80,
+        }
+    }
+    // the relative input paths are resolved by the last `-X base_dir=`.
+    pub fn resolve_path(&self, path: &str) -> String {
+        match self.opt_uc_x_base_dir() {
+            Some(base_dir) if path != "-" && std::path::Path::new(path).is_relative() => {
+                std::path::Path::new(base_dir)
+                    .join(path)
+                    .to_string_lossy()
+                    .into_owned()
+            }
+            _ => path.to_string(),
+        }
+    }
+    pub fn opt_uc_x_base_dir(&self) -> Option<&str> {
+        self.opt_uc_x.iter().rev().find_map(|o| match o {
+            OptUcXParam::BaseDir(s) => Some(s.as_str()),
+            _ => None,
+        })
+    }
     pub fn is_opt_uc_x_help(&self) -> bool {
         for o in self.opt_uc_x.iter() {
             if let OptUcXParam::Help = o {
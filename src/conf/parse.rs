@@ -4,7 +4,16 @@ use flood_tide::HelpVersion;
 use flood_tide::{Arg, NameVal, Opt, OptNum};
 use flood_tide::{OptParseError, OptParseErrors};
 
+use crate::util::OptCodeLang;
+use crate::util::OptGroupBy;
+use crate::util::OptHashAlg;
 use crate::util::OptLocaleLoc;
+use crate::util::OptLogBucket;
+use crate::util::OptMapAsciiMode;
+use crate::util::OptMapSort;
+use crate::util::OptTableLang;
+use crate::util::OptTableNorm;
+use crate::util::OptTableType;
 use crate::util::OptUcXParam;
 use num_format::Locale;
 use std::str::FromStr;
@@ -16,11 +25,9 @@ include!("cmd.help.rs.txt");
 const DESCRIPTIONS_TEXT: &str = r#"
 output the statistics of text, like a wc of linux command.
 "#;
-/*
-const ARGUMENTS_TEXT: &str = r#"Argument:
-  <url>                     url to getting, protocol is http or ftp
+const ARGUMENTS_TEXT: &str = r#"Arguments:
+  <file>...                 the input files. -: stdin. default: stdin
 "#;
-*/
 const EXAMPLES_TEXT: &str = r#"Examples:
   Outputs the line count:
     echo -e "acbde fghi\njkln opqr" | aki-stats -l
@@ -40,14 +47,14 @@ fn version_message(_program: &str) -> String {
 
 #[rustfmt::skip]
 fn usage_message(program: &str) -> String {
-    format!("Usage:\n  {} {}", program, "[options]")
+    format!("Usage:\n  {} {}", program, "[options] [<file>...]")
 }
 
 #[rustfmt::skip]
 fn help_message(program: &str) -> String {
     let ver = version_message(program);
     let usa = usage_message(env!("CARGO_PKG_NAME"));
-    [ &ver, "", &usa, DESCRIPTIONS_TEXT, OPTIONS_TEXT, EXAMPLES_TEXT].join("\n")
+    [ &ver, "", &usa, DESCRIPTIONS_TEXT, ARGUMENTS_TEXT, OPTIONS_TEXT, EXAMPLES_TEXT].join("\n")
 }
 
 #[rustfmt::skip]
@@ -56,6 +63,7 @@ fn opt_uc_x_help_message(_program: &str) -> String {
         "Options:\n",
         "  -X rust-version-info     display rust version info and exit\n",
         "  -X map-ascii-rust-src    output rust source of the ascii map statistics\n",
+        "  -X base_dir=<path>       base directory of the relative input paths\n",
     );
     z_opts.to_string()
 }
@@ -102,14 +110,35 @@ fn parse_match(conf: &mut CmdOptConf, nv: &NameVal<'_>) -> Result<(), OptParseEr
     Ok(())
 }
 
+// the bare "-" of stdin is dropped by flood-tide as an empty short option,
+// so it is passed through the parser as this placeholder.
+const STDIN_ARG: &str = "\0-";
+
 pub fn parse_cmdopts(a_prog_name: &str, args: &[&str]) -> Result<CmdOptConf, OptParseErrors> {
     //
     let mut conf = CmdOptConf {
         prog_name: a_prog_name.to_string(),
+        opt_locations: 3,
+        opt_dup_top: 10,
+        opt_threads: 1,
         ..Default::default()
     };
+    let args: Vec<&str> = args
+        .iter()
+        .map(|&arg| if arg == "-" { STDIN_ARG } else { arg })
+        .collect();
     let (opt_free, r_errs) =
-        parse_simple_gnu_style(&mut conf, &OPT_ARY, &OPT_ARY_SHO_IDX, args, parse_match);
+        parse_simple_gnu_style(&mut conf, &OPT_ARY, &OPT_ARY_SHO_IDX, &args, |conf, nv| {
+            if nv.val == Some(STDIN_ARG) {
+                let nv = NameVal {
+                    opt: nv.opt,
+                    val: Some("-"),
+                };
+                parse_match(conf, &nv)
+            } else {
+                parse_match(conf, nv)
+            }
+        });
     //
     if conf.is_help() {
         let mut errs = OptParseErrors::new();
@@ -139,9 +168,8 @@ pub fn parse_cmdopts(a_prog_name: &str, args: &[&str]) -> Result<CmdOptConf, Opt
             return Err(errs);
         }
     }
-    if conf.opt_query.is_some() {
+    if let Some(s) = conf.opt_query {
         let mut errs = OptParseErrors::new();
-        let s = conf.opt_query.unwrap();
         match s.as_str() {
             "locale" => {
                 errs.push(OptParseError::version_message(&query_locale(
@@ -171,20 +199,81 @@ pub fn parse_cmdopts(a_prog_name: &str, args: &[&str]) -> Result<CmdOptConf, Opt
             conf.flg_lines = true;
             conf.flg_words = true;
             conf.flg_max_line_bytes = true;
-        } else if !conf.flg_map_ascii
+        } else if !conf.is_map_ascii()
             && !conf.flg_bytes
             && !conf.flg_chars
             && !conf.flg_lines
             && !conf.flg_words
+            && !conf.flg_blank
+            && !conf.flg_ws_only
+            && !conf.flg_trailing_ws
+            && !conf.flg_indent
+            && !conf.flg_invisibles
+            && conf.opt_emit_table.is_none()
+            && conf.opt_pattern.is_empty()
+            && !conf.flg_dup_lines
+            && conf.opt_hash.is_none()
+            && !conf.flg_fields
+            && conf.opt_numeric_field.is_none()
+            && conf.opt_code.is_none()
+            && !conf.flg_markdown
+            && !conf.flg_log
+            && !conf.flg_compressed
         {
             errs.push(OptParseError::missing_option(
-                "b, c, l, w, a or --map-ascii",
+                "b, c, l, w, a or another statistics option",
+            ));
+        }
+        if cfg!(not(feature = "decompress")) && conf.flg_decompress {
+            errs.push(OptParseError::invalid_option(
+                "--decompress: needs the `decompress` feature of the build",
+            ));
+        }
+        if conf.flg_compressed && !conf.flg_decompress {
+            errs.push(OptParseError::invalid_option(
+                "--compressed: needs --decompress",
+            ));
+        }
+        if !conf.opt_table_name.is_empty() && !is_identifier(&conf.opt_table_name) {
+            errs.push(OptParseError::invalid_option_argument(
+                "table-name",
+                &format!("'{}' is not an identifier", conf.opt_table_name),
+            ));
+        }
+        if conf.opt_table_norm == OptTableNorm::Log && conf.opt_table_type != OptTableType::F32 {
+            errs.push(OptParseError::invalid_option_argument(
+                "table-norm",
+                "'log' needs the table type 'f32'",
+            ));
+        }
+        if !conf.opt_delimiter.is_empty() && conf.opt_delimiter.chars().count() != 1 {
+            errs.push(OptParseError::invalid_option_argument(
+                "delimiter",
+                &format!("'{}' is not a character", conf.opt_delimiter),
+            ));
+        }
+        if conf.opt_numeric_field == Some(0) {
+            errs.push(OptParseError::invalid_option_argument(
+                "numeric-field",
+                "the field number starts from 1",
+            ));
+        }
+        if conf.opt_dup_approx == Some(0) {
+            errs.push(OptParseError::invalid_option_argument(
+                "dup-approx",
+                "needs one or more counters",
             ));
         }
         //
         if let Some(free) = opt_free {
-            if !free.is_empty() {
-                errs.push(OptParseError::unexpected_argument(&free[0]));
+            for path in free {
+                if path.is_empty() {
+                    errs.push(OptParseError::unexpected_argument(&path));
+                } else if path == STDIN_ARG {
+                    conf.arg_params.push("-".to_string());
+                } else {
+                    conf.arg_params.push(path);
+                }
             }
         };
         if !errs.is_empty() {
@@ -194,3 +283,13 @@ pub fn parse_cmdopts(a_prog_name: &str, args: &[&str]) -> Result<CmdOptConf, Opt
     //
     Ok(conf)
 }
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
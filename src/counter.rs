@@ -0,0 +1,115 @@
+use crate::stats::{Stats, StatsConfig};
+
+/// The incremental counter of the statistics.
+///
+/// The text is fed in the byte chunks of any size, the lines and
+/// the utf-8 sequences split across the chunks are counted as joined.
+/// The counters of the consecutive shards are merged into the counter
+/// of the concatenated text.
+///
+/// The invalid utf-8 sequences are counted as the replacement characters,
+/// and the record separator of the configuration is not used.
+///
+/// # Examples
+///
+/// ```rust
+/// use libaki_stats::Counter;
+///
+/// let mut a = Counter::default();
+/// a.update(b"abc de");
+/// let mut b = Counter::default();
+/// b.update("f\n\u{3042}\n".as_bytes());
+/// a.merge(b);
+/// let stats = a.stats();
+/// assert_eq!(stats.line_count, 2);
+/// assert_eq!(stats.word_count, 3);
+/// assert_eq!(stats.char_count, 8);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Counter {
+    config: StatsConfig,
+    newline_seen: bool,
+    // the first line, kept raw to be joined with the previous shard
+    head: Vec<u8>,
+    // the statistics of the complete lines after the first line
+    body: Stats,
+    // the bytes after the last newline
+    tail: Vec<u8>,
+}
+
+impl Counter {
+    pub fn new(config: StatsConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+    /// feed the byte chunk.
+    pub fn update(&mut self, buf: &[u8]) {
+        let mut rest = buf;
+        while let Some(pos) = rest.iter().position(|&b| b == b'\n') {
+            let line = &rest[..pos];
+            if self.newline_seen {
+                if self.tail.is_empty() {
+                    count_up_bytes(&mut self.body, line, &self.config);
+                } else {
+                    self.tail.extend_from_slice(line);
+                    let tail = std::mem::take(&mut self.tail);
+                    count_up_bytes(&mut self.body, &tail, &self.config);
+                }
+            } else {
+                self.head.append(&mut self.tail);
+                self.head.extend_from_slice(line);
+                self.newline_seen = true;
+            }
+            rest = &rest[pos + 1..];
+        }
+        self.tail.extend_from_slice(rest);
+    }
+    /// merge the counter of the following text.
+    pub fn merge(&mut self, other: Counter) {
+        let Counter {
+            newline_seen,
+            head,
+            body,
+            tail,
+            ..
+        } = other;
+        if !newline_seen {
+            // the other is a part of the last line
+            self.tail.extend_from_slice(&tail);
+            return;
+        }
+        self.tail.extend_from_slice(&head);
+        // the last line of self and the first line of other are joined.
+        let line = std::mem::take(&mut self.tail);
+        if self.newline_seen {
+            count_up_bytes(&mut self.body, &line, &self.config);
+        } else {
+            self.head = line;
+            self.newline_seen = true;
+        }
+        self.body.merge(&body);
+        self.tail = tail;
+    }
+    /// the statistics of the text fed so far.
+    ///
+    /// the bytes after the last newline are counted as the last line.
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats::default();
+        if self.newline_seen {
+            count_up_bytes(&mut stats, &self.head, &self.config);
+            stats.merge(&self.body);
+        }
+        if !self.tail.is_empty() {
+            count_up_bytes(&mut stats, &self.tail, &self.config);
+        }
+        stats
+    }
+}
+
+fn count_up_bytes(stats: &mut Stats, line: &[u8], config: &StatsConfig) {
+    // the same as `BufRead::lines()`, the "\r\n" is a newline.
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    stats.count_up(&String::from_utf8_lossy(line), config);
+}
@@ -4,7 +4,9 @@ output the statistics of text, like a wc of linux command.
 # Features
 
 - output the statistics of text, like a wc of linux command.
-- minimum support rustc 1.65.0 (897e37553 2022-11-02)
+- minimum support rustc 1.88.0 (6b00bc388 2025-06-23)
+- `--decompress` of gzip, zstd, xz and bzip2 with the `decompress` feature:
+  `cargo install aki-stats --features decompress`
 
 # Command help
 
@@ -14,22 +16,71 @@ aki-stats --help
 
 ```text
 Usage:
-  aki-stats [options]
+  aki-stats [options] [<file>...]
 
 output the statistics of text, like a wc of linux command.
 
+Arguments:
+  <file>...                 the input files. -: stdin. default: stdin
+
 Options:
-  -a, --all                 output the all statistics of text
+  -a, --all                 output the all statistics of text, exclude ascii map
+      --blank               output the empty line counts
   -b, --bytes               output the byte counts
   -c, --chars               output the unicode character counts
+      --dup-lines           output the distinct and the repeated (uniq -d) line counts
+  -e, --pattern <regex>     count the matching lines and occurrences of regex
+  -F, --fixed-strings       interpret the patterns as the literal strings
+      --fields              output the field statistics of the delimited text
+      --indent              output the indentation statistics
+      --invisibles          output the control and invisible character report
   -l, --lines               output the line counts
+      --log                 output the log statistics by level and time
+      --map-ascii[=<mode>]  output the ascii map statistics: plain, detailed, nonzero
+      --map-bar             output the bar chart of the ascii map
+      --map-percent         output the percentages of the ascii map
+      --map-sort <order>    order of the ascii map: code, count
+      --map-width <num>     width of the ascii map bar lines. default: $COLUMNS or 80
+      --markdown            output the markdown document statistics
   -m, --max-line-bytes      output the maximum byte counts of line
   -w, --words               output the word counts
+      --trailing-ws         output the line counts ending in spaces or tabs
+      --ws-only             output the whitespace-only line counts
       --locale <loc>        locale of number format: en, fr, ... posix
   -?, --query <q>           display available names of locale and exit
+      --locations <num>     locations per invisible character. default: 3
+      --record-sep <regex>  record separator regex instead of newline
+      --record-str <str>    record separator string instead of newline
+      --word-delims <chars> extra characters that delimit words
+      --emit-table <lang>   output the ascii map table source: rust, c, python, json
+      --table-name <name>   identifier of the table. default: ASCII_STOCHAS
+      --table-type <type>   element type of the table: u8, u16, f32
+      --table-norm <norm>   normalisation of the table: max, sum, log
+      --code <lang>         classify the lines of code: auto, rust, c, python, shell, toml
+      --hash <alg>          output the digest of the input: sha256, blake3, crc32, xxh3
+      --log-bucket <unit>   time bucket of --log: hour, minute
+  -d, --delimiter <char>    field delimiter of --fields. default: ','
+  -t, --tab                 use the tab as the field delimiter of --fields
+      --numeric-field <num> output the numeric summary of the field number
+      --threads <num>       threads to count the files and a large file. 0: the cpus. default: 1
+      --mmap                read the regular files through the memory map
+      --decompress          decompress the inputs of gzip, zstd, xz and bzip2
+      --compressed          output the compressed byte counts and ratio of --decompress
+  -r, --recursive           count the files in the directories recursively
+      --include <glob>      count only the files matching the glob of -r
+      --exclude <glob>      skip the files matching the glob of -r
+      --gitignore           skip the files ignored by .gitignore of -r
+      --files-from <path>   read the input files from the lines of path. -: stdin
+      --files0-from <path>  read the NUL separated input files of path. -: stdin
+      --group-by <key>      subtotals of the files by the key: ext, dir, depth=N
+      --dup-top <num>       top repeated lines of --dup-lines. default: 10
+      --dup-trim            trim the white spaces of lines for --dup-lines
+      --dup-fold            fold the case of lines for --dup-lines
+      --dup-approx <num>    approximate --dup-lines with <num> counters
 
   -H, --help        display this help and exit
   -V, --version     display version information and exit
+  -X <x-options>    x options. try -X help
 
 Examples:
   Outputs the line count:
@@ -104,15 +155,32 @@ bytes:"1,207"
 
 See [`fn execute()`] for this library examples.
 
+The typed statistics are computed by [`fn compute()`] without the command line.
+
+```rust
+use libaki_stats::{compute, StatsConfig};
+
+let config = StatsConfig::builder().word_delims(",").build().unwrap();
+let stats = compute("a,b c\nd\n".as_bytes(), &config).unwrap();
+assert_eq!(stats.line_count, 2);
+assert_eq!(stats.word_count, 4);
+```
+
 [`fn execute()`]: crate::execute
+[`fn compute()`]: crate::compute
 */
 #[macro_use]
 extern crate anyhow;
 
 mod conf;
+mod counter;
 mod run;
+mod stats;
 mod util;
 
+pub use counter::Counter;
+pub use stats::{compute, Stats, StatsConfig, StatsConfigBuilder};
+
 use flood_tide::HelpVersion;
 use runnel::RunnelIoe;
 
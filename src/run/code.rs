@@ -0,0 +1,274 @@
+use crate::util::OptCodeLang;
+
+struct LangSpec {
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    nested_block: bool,
+    // the longer delimiter is first, the triple quotes can span lines.
+    strings: &'static [&'static str],
+    // the line comment starts at the beginning of a word, like shell's '#'
+    comment_at_word: bool,
+    // the strings in the single quote character can span lines
+    multiline_strings: bool,
+    // the rust's char literals, to tell them from the lifetimes
+    char_literals: bool,
+    // the rust's raw strings, like r#"..."#, without the escapes
+    raw_strings: bool,
+}
+
+const LANG_RUST: LangSpec = LangSpec {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    nested_block: true,
+    strings: &["\""],
+    comment_at_word: false,
+    multiline_strings: true,
+    char_literals: true,
+    raw_strings: true,
+};
+const LANG_C: LangSpec = LangSpec {
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    nested_block: false,
+    strings: &["\"", "'"],
+    comment_at_word: false,
+    multiline_strings: false,
+    char_literals: false,
+    raw_strings: false,
+};
+const LANG_PYTHON: LangSpec = LangSpec {
+    line_comments: &["#"],
+    block_comment: None,
+    nested_block: false,
+    strings: &["\"\"\"", "'''", "\"", "'"],
+    comment_at_word: false,
+    multiline_strings: false,
+    char_literals: false,
+    raw_strings: false,
+};
+const LANG_SHELL: LangSpec = LangSpec {
+    line_comments: &["#"],
+    block_comment: None,
+    nested_block: false,
+    strings: &["\"", "'"],
+    comment_at_word: true,
+    multiline_strings: true,
+    char_literals: false,
+    raw_strings: false,
+};
+const LANG_TOML: LangSpec = LangSpec {
+    line_comments: &["#"],
+    block_comment: None,
+    nested_block: false,
+    strings: &["\"\"\"", "'''", "\"", "'"],
+    comment_at_word: false,
+    multiline_strings: false,
+    char_literals: false,
+    raw_strings: false,
+};
+
+fn lang_spec(lang: OptCodeLang) -> Option<&'static LangSpec> {
+    match lang {
+        OptCodeLang::Rust => Some(&LANG_RUST),
+        OptCodeLang::C => Some(&LANG_C),
+        OptCodeLang::Python => Some(&LANG_PYTHON),
+        OptCodeLang::Shell => Some(&LANG_SHELL),
+        OptCodeLang::Toml => Some(&LANG_TOML),
+        OptCodeLang::Auto => None,
+    }
+}
+
+// the language of `--code auto` by the file extension.
+fn lang_of_path(path: &str) -> Option<OptCodeLang> {
+    let ext = std::path::Path::new(path).extension()?.to_str()?;
+    let lang = match ext.to_ascii_lowercase().as_str() {
+        "rs" => OptCodeLang::Rust,
+        "c" | "h" | "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => OptCodeLang::C,
+        "py" | "pyi" => OptCodeLang::Python,
+        "sh" | "bash" | "zsh" | "ksh" => OptCodeLang::Shell,
+        "toml" => OptCodeLang::Toml,
+        _ => return None,
+    };
+    Some(lang)
+}
+
+#[derive(Clone)]
+pub(super) struct StatsCode {
+    spec: &'static LangSpec,
+    block_depth: usize,
+    in_string: Option<&'static str>,
+    // the number of '#' of the raw string continued to the next line
+    in_raw_string: Option<usize>,
+    pub(super) code_count: u64,
+    pub(super) comment_count: u64,
+    pub(super) blank_count: u64,
+}
+
+impl StatsCode {
+    // returns None for `--code auto` of the stdin or of an unknown extension,
+    // its code labels are output as "-".
+    pub(super) fn new(lang: OptCodeLang, path: Option<&str>) -> Option<Self> {
+        let lang = match lang {
+            OptCodeLang::Auto => lang_of_path(path?)?,
+            _ => lang,
+        };
+        Some(Self {
+            spec: lang_spec(lang)?,
+            block_depth: 0,
+            in_string: None,
+            in_raw_string: None,
+            code_count: 0,
+            comment_count: 0,
+            blank_count: 0,
+        })
+    }
+    pub(super) fn merge(&mut self, other: &Self) {
+        self.code_count += other.code_count;
+        self.comment_count += other.comment_count;
+        self.blank_count += other.blank_count;
+    }
+    pub(super) fn count_up(&mut self, line_ss: &str) {
+        if line_ss.trim().is_empty() && self.in_string.is_none() && self.in_raw_string.is_none() {
+            self.blank_count += 1;
+            return;
+        }
+        let (has_code, has_comment) = self.scan_line(line_ss);
+        if has_code {
+            self.code_count += 1;
+        } else if has_comment {
+            self.comment_count += 1;
+        } else {
+            self.blank_count += 1;
+        }
+    }
+    // returns (has_code, has_comment) of the line, and keeps the state
+    // of the block comment and the string continued to the next line.
+    fn scan_line(&mut self, line_ss: &str) -> (bool, bool) {
+        let spec = self.spec;
+        let mut has_code = false;
+        let mut has_comment = false;
+        let mut prev_is_space = true;
+        let mut prev_is_ident = false;
+        let mut i = 0;
+        while i < line_ss.len() {
+            let rest = &line_ss[i..];
+            if self.block_depth > 0 {
+                has_comment = true;
+                let (start, end) = spec.block_comment.unwrap();
+                if rest.starts_with(end) {
+                    self.block_depth -= 1;
+                    i += end.len();
+                } else if spec.nested_block && rest.starts_with(start) {
+                    self.block_depth += 1;
+                    i += start.len();
+                } else {
+                    i += char_len(rest);
+                }
+                continue;
+            }
+            if let Some(hashes) = self.in_raw_string {
+                has_code = true;
+                if rest.starts_with('"')
+                    && rest[1..].bytes().take_while(|&b| b == b'#').count() >= hashes
+                {
+                    self.in_raw_string = None;
+                    i += 1 + hashes;
+                } else {
+                    i += char_len(rest);
+                }
+                continue;
+            }
+            if let Some(delim) = self.in_string {
+                has_code = true;
+                if rest.starts_with('\\') {
+                    i += 1;
+                    i += char_len(&line_ss[i..]);
+                } else if rest.starts_with(delim) {
+                    self.in_string = None;
+                    i += delim.len();
+                } else {
+                    i += char_len(rest);
+                }
+                continue;
+            }
+            let c = rest.chars().next().unwrap();
+            if c.is_whitespace() {
+                prev_is_space = true;
+                prev_is_ident = false;
+                i += c.len_utf8();
+                continue;
+            }
+            if spec
+                .line_comments
+                .iter()
+                .any(|s| rest.starts_with(s) && (prev_is_space || !spec.comment_at_word))
+            {
+                has_comment = true;
+                break;
+            }
+            if let Some((start, _)) = spec.block_comment {
+                if rest.starts_with(start) {
+                    self.block_depth = 1;
+                    has_comment = true;
+                    i += start.len();
+                    continue;
+                }
+            }
+            has_code = true;
+            prev_is_space = false;
+            if spec.raw_strings && !prev_is_ident {
+                if let Some((len, hashes)) = raw_string_start(rest) {
+                    self.in_raw_string = Some(hashes);
+                    i += len;
+                    continue;
+                }
+            }
+            prev_is_ident = c.is_alphanumeric() || c == '_';
+            if spec.char_literals && c == '\'' {
+                i += char_literal_len(rest).unwrap_or(1);
+                continue;
+            }
+            if let Some(delim) = spec.strings.iter().find(|s| rest.starts_with(**s)) {
+                self.in_string = Some(delim);
+                i += delim.len();
+                continue;
+            }
+            i += c.len_utf8();
+        }
+        if let Some(delim) = self.in_string {
+            if delim.len() == 1 && !spec.multiline_strings {
+                self.in_string = None;
+            }
+        }
+        (has_code, has_comment)
+    }
+}
+
+// the length of the char literal at the beginning, like 'a' or '\n'.
+fn char_literal_len(s: &str) -> Option<usize> {
+    let mut chars = s.char_indices().skip(1);
+    match chars.next()? {
+        (_, '\\') => chars.take(10).find(|(_, c)| *c == '\'').map(|(i, _)| i + 1),
+        (_, '\'') => None,
+        _ => match chars.next()? {
+            (i, '\'') => Some(i + 1),
+            _ => None,
+        },
+    }
+}
+
+// the length of the start of the raw string, like r#" or br", and the number of '#'.
+fn raw_string_start(s: &str) -> Option<(usize, usize)> {
+    let rest = s.strip_prefix(['b', 'c']).unwrap_or(s);
+    let rest = rest.strip_prefix('r')?;
+    let hashes = rest.bytes().take_while(|&b| b == b'#').count();
+    if rest[hashes..].starts_with('"') {
+        Some((s.len() - rest.len() + hashes + 1, hashes))
+    } else {
+        None
+    }
+}
+
+fn char_len(s: &str) -> usize {
+    s.chars().next().map_or(1, char::len_utf8)
+}
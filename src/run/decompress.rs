@@ -0,0 +1,190 @@
+#[cfg(feature = "decompress")]
+use ruzstd::decoding::errors::{FrameDecoderError, ReadFrameHeaderError};
+#[cfg(feature = "decompress")]
+use ruzstd::decoding::{BlockDecodingStrategy, FrameDecoder};
+use std::cell::Cell;
+use std::io::BufRead;
+#[cfg(feature = "decompress")]
+use std::io::{BufReader, Cursor, Read};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Gzip,
+    Zstd,
+    Xz,
+    Bzip2,
+}
+
+impl Format {
+    #[cfg(feature = "decompress")]
+    const MAGIC_MAX: usize = 10;
+    fn detect(head: &[u8]) -> Option<Self> {
+        if head.starts_with(b"\x1f\x8b") {
+            Some(Format::Gzip)
+        } else if head.starts_with(b"\x28\xb5\x2f\xfd") {
+            Some(Format::Zstd)
+        } else if head.starts_with(b"\xfd7zXZ\x00") {
+            Some(Format::Xz)
+        } else if is_bzip2(head) {
+            Some(Format::Bzip2)
+        } else {
+            None
+        }
+    }
+}
+
+// "BZh", the block size of '1' to '9', and the magic of the first block
+// or of the end of the empty stream, not to take a text like "BZhello".
+fn is_bzip2(head: &[u8]) -> bool {
+    match head.strip_prefix(b"BZh") {
+        Some([b'1'..=b'9', rest @ ..]) => {
+            rest.starts_with(b"1AY&SY") || rest.starts_with(b"\x17rE8P\x90")
+        }
+        _ => false,
+    }
+}
+
+pub(super) fn is_compressed(head: &[u8]) -> bool {
+    Format::detect(head).is_some()
+}
+
+// the compressed and the decompressed byte counts of the input.
+#[derive(Debug, Clone, Default)]
+pub(super) struct DecompressSizes {
+    compressed: Rc<Cell<u64>>,
+    decompressed: Rc<Cell<u64>>,
+}
+
+impl DecompressSizes {
+    pub(super) fn get(&self) -> (u64, u64) {
+        (self.compressed.get(), self.decompressed.get())
+    }
+}
+
+#[cfg(feature = "decompress")]
+struct CountRead<R> {
+    inner: R,
+    count: Rc<Cell<u64>>,
+}
+
+#[cfg(feature = "decompress")]
+impl<R: Read> Read for CountRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.set(self.count.get() + n as u64);
+        Ok(n)
+    }
+}
+
+// the input is decompressed when it starts with the magic bytes of
+// gzip, zstd, xz or bzip2, otherwise it is returned as is.
+#[cfg(feature = "decompress")]
+pub(super) fn decompress_reader<'a>(
+    mut reader: Box<dyn BufRead + 'a>,
+) -> anyhow::Result<(Box<dyn BufRead + 'a>, Option<DecompressSizes>)> {
+    let mut head = Vec::with_capacity(Format::MAGIC_MAX);
+    (&mut reader)
+        .take(Format::MAGIC_MAX as u64)
+        .read_to_end(&mut head)?;
+    let format = Format::detect(&head);
+    let reader = Cursor::new(head).chain(reader);
+    let format = match format {
+        Some(format) => format,
+        None => return Ok((Box::new(reader), None)),
+    };
+    let sizes = DecompressSizes::default();
+    let reader = BufReader::new(CountRead {
+        inner: reader,
+        count: sizes.compressed.clone(),
+    });
+    let decoder: Box<dyn Read + 'a> = match format {
+        Format::Gzip => Box::new(flate2::bufread::MultiGzDecoder::new(reader)),
+        Format::Zstd => Box::new(ZstdDecoder::new(reader)?),
+        Format::Xz => Box::new(xz2::bufread::XzDecoder::new_multi_decoder(reader)),
+        Format::Bzip2 => Box::new(bzip2::bufread::MultiBzDecoder::new(reader)),
+    };
+    let decoder = CountRead {
+        inner: decoder,
+        count: sizes.decompressed.clone(),
+    };
+    Ok((Box::new(BufReader::new(decoder)), Some(sizes)))
+}
+
+// `--decompress` is rejected by `parse_cmdopts()` without the feature.
+#[cfg(not(feature = "decompress"))]
+pub(super) fn decompress_reader<'a>(
+    reader: Box<dyn BufRead + 'a>,
+) -> anyhow::Result<(Box<dyn BufRead + 'a>, Option<DecompressSizes>)> {
+    Ok((reader, None))
+}
+
+// the zstd frames concatenated like `cat a.zst b.zst` are decoded one by one,
+// because `ruzstd::decoding::StreamingDecoder` decodes only the first frame.
+#[cfg(feature = "decompress")]
+struct ZstdDecoder<R> {
+    source: R,
+    frame: FrameDecoder,
+}
+
+#[cfg(feature = "decompress")]
+impl<R: BufRead> ZstdDecoder<R> {
+    fn new(source: R) -> std::io::Result<Self> {
+        let mut decoder = Self {
+            source,
+            frame: FrameDecoder::new(),
+        };
+        decoder.next_frame()?;
+        Ok(decoder)
+    }
+    // returns false at the end of the input.
+    fn next_frame(&mut self) -> std::io::Result<bool> {
+        loop {
+            if self.source.fill_buf()?.is_empty() {
+                return Ok(false);
+            }
+            match self.frame.reset(&mut self.source) {
+                Ok(()) => return Ok(true),
+                Err(FrameDecoderError::ReadFrameHeaderError(ReadFrameHeaderError::SkipFrame {
+                    length,
+                    ..
+                })) => {
+                    let mut skipped = (&mut self.source).take(u64::from(length));
+                    std::io::copy(&mut skipped, &mut std::io::sink())?;
+                }
+                Err(err) => return Err(zstd_error(err)),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "decompress")]
+impl<R: BufRead> Read for ZstdDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            if self.frame.can_collect() > 0 {
+                return self.frame.read(buf);
+            }
+            if !self.frame.is_finished() {
+                self.frame
+                    .decode_blocks(
+                        &mut self.source,
+                        BlockDecodingStrategy::UptoBytes(buf.len()),
+                    )
+                    .map_err(zstd_error)?;
+                continue;
+            }
+            if !self.next_frame()? {
+                return Ok(0);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "decompress")]
+fn zstd_error(err: FrameDecoderError) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, format!("zstd: {err}"))
+}
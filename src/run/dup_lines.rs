@@ -0,0 +1,175 @@
+use crate::conf::CmdOptConf;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
+
+#[derive(Default)]
+pub(super) struct StatsDupLines {
+    trim: bool,
+    fold: bool,
+    line_count: u64,
+    // the exact counts, or the space-saving counters in the approximate mode
+    counts: HashMap<String, u64>,
+    approx: Option<DupApprox>,
+}
+
+// the memory-bounded approximation:
+// the space-saving algorithm for the top lines and
+// the hyperloglog for the distinct line counts.
+struct DupApprox {
+    capacity: usize,
+    // (count, line) of the counters, to evict the least one
+    order: BTreeSet<(u64, String)>,
+    // the over-estimation of the counters
+    errors: HashMap<String, u64>,
+    hll: HyperLogLog,
+}
+
+impl StatsDupLines {
+    pub(super) fn new(conf: &CmdOptConf) -> Self {
+        let approx = conf.opt_dup_approx.map(|capacity| DupApprox {
+            capacity,
+            order: BTreeSet::new(),
+            errors: HashMap::new(),
+            hll: HyperLogLog::new(),
+        });
+        Self {
+            trim: conf.flg_dup_trim,
+            fold: conf.flg_dup_fold,
+            approx,
+            ..Default::default()
+        }
+    }
+    pub(super) fn count_up(&mut self, line_ss: &str) {
+        self.line_count += 1;
+        let line_ss = if self.trim { line_ss.trim() } else { line_ss };
+        let key = if self.fold {
+            line_ss.to_lowercase()
+        } else {
+            line_ss.to_string()
+        };
+        match self.approx {
+            None => *self.counts.entry(key).or_insert(0) += 1,
+            Some(ref mut approx) => {
+                approx.hll.insert(&key);
+                let len = self.counts.len();
+                let count = match self.counts.get_mut(&key) {
+                    Some(count) => {
+                        approx.order.remove(&(*count, key.clone()));
+                        *count += 1;
+                        *count
+                    }
+                    None if len < approx.capacity => {
+                        self.counts.insert(key.clone(), 1);
+                        1
+                    }
+                    None => {
+                        // replaces the least counter, inheriting its count
+                        let least = approx.order.iter().next().cloned().unwrap();
+                        approx.order.remove(&least);
+                        let (min, min_key) = least;
+                        self.counts.remove(&min_key);
+                        approx.errors.remove(&min_key);
+                        approx.errors.insert(key.clone(), min);
+                        self.counts.insert(key.clone(), min + 1);
+                        min + 1
+                    }
+                };
+                approx.order.insert((count, key));
+            }
+        }
+    }
+    fn distinct(&self) -> u64 {
+        match self.approx {
+            None => self.counts.len() as u64,
+            Some(ref approx) => approx.hll.estimate().min(self.line_count),
+        }
+    }
+    // the distinct lines that are repeated, as `uniq -d`.
+    // the approximate counters are counted only if they are surely repeated,
+    // then it is the lower bound.
+    fn duplicated(&self) -> u64 {
+        self.counts
+            .iter()
+            .filter(|(line, c)| **c - self.count_error(line) > 1)
+            .count() as u64
+    }
+    fn count_error(&self, line: &str) -> u64 {
+        match self.approx {
+            None => 0,
+            Some(ref approx) => approx.errors.get(line).copied().unwrap_or(0),
+        }
+    }
+    pub(super) fn make_out_s(&self, conf: &CmdOptConf) -> anyhow::Result<Vec<String>> {
+        let loc = &conf.opt_locale;
+        // the approximate values are marked with '~'
+        let mark = if self.approx.is_some() { "~" } else { "" };
+        let distinct = self.distinct();
+        let mut vec: Vec<String> = Vec::new();
+        vec.push(format!(
+            "dup-lines: distinct: {mark}{}",
+            loc.formatted_string(distinct)
+        ));
+        vec.push(format!(
+            "dup-lines: duplicated: {mark}{}",
+            loc.formatted_string(self.duplicated())
+        ));
+        // the approximate counters are shown only if they are surely repeated.
+        let mut tops: Vec<(&String, &u64)> = self
+            .counts
+            .iter()
+            .filter(|(line, c)| **c - self.count_error(line) > 1)
+            .collect();
+        tops.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        for (line, count) in tops.iter().take(conf.opt_dup_top) {
+            vec.push(format!(
+                "dup-lines: top: {mark}{}: {}",
+                loc.formatted_string(**count),
+                line
+            ));
+        }
+        Ok(vec)
+    }
+}
+
+const HLL_BITS: u32 = 12;
+const HLL_SIZE: usize = 1 << HLL_BITS;
+
+struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    fn new() -> Self {
+        Self {
+            registers: vec![0; HLL_SIZE],
+        }
+    }
+    fn insert(&mut self, s: &str) {
+        let mut hasher = DefaultHasher::new();
+        s.hash(&mut hasher);
+        let h = hasher.finish();
+        let idx = (h >> (64 - HLL_BITS)) as usize;
+        let rank = ((h << HLL_BITS).leading_zeros() + 1).min(64 - HLL_BITS + 1) as u8;
+        if self.registers[idx] < rank {
+            self.registers[idx] = rank;
+        }
+    }
+    fn estimate(&self) -> u64 {
+        let m = HLL_SIZE as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&r| 1.0 / (1u64 << r) as f64)
+            .sum();
+        let est = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if est <= 2.5 * m && zeros > 0 {
+            // the linear counting for the small cardinalities
+            (m * (m / zeros as f64).ln()).round() as u64
+        } else {
+            est.round() as u64
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::conf::CmdOptConf;
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Default)]
+struct ColumnRec {
+    non_empty: u64,
+    distinct: HashSet<String>,
+    min_width: Option<usize>,
+    max_width: usize,
+}
+
+#[derive(Default)]
+pub(super) struct StatsFields {
+    delimiter: char,
+    row_count: u64,
+    // field count -> row count
+    field_counts: BTreeMap<usize, u64>,
+    columns: Vec<ColumnRec>,
+    // the record continued from the previous line in the quoted field
+    pending: Option<String>,
+    numeric: StatsNumeric,
+}
+
+#[derive(Default)]
+struct StatsNumeric {
+    // the index of the field, 0-origin
+    field_idx: Option<usize>,
+    values: Vec<f64>,
+    skipped: u64,
+}
+
+impl StatsFields {
+    pub(super) fn new(delimiter: char, numeric_field: Option<usize>) -> Self {
+        Self {
+            delimiter,
+            numeric: StatsNumeric {
+                field_idx: numeric_field.map(|n| n - 1),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+    pub(super) fn count_up(&mut self, line_ss: &str) {
+        let record = match self.pending.take() {
+            Some(mut s) => {
+                s.push('\n');
+                s.push_str(line_ss);
+                s
+            }
+            None if line_ss.is_empty() => return,
+            None => line_ss.to_string(),
+        };
+        match split_fields(&record, self.delimiter) {
+            Some(fields) => self.count_up_row(fields),
+            None => self.pending = Some(record),
+        }
+    }
+    // the unterminated quoted field at the end of the input is a row.
+    pub(super) fn finish(&mut self) {
+        if let Some(record) = self.pending.take() {
+            let fields = split_fields_lenient(&record, self.delimiter);
+            self.count_up_row(fields);
+        }
+    }
+    fn count_up_row(&mut self, fields: Vec<String>) {
+        self.numeric.count_up(&fields);
+        self.row_count += 1;
+        *self.field_counts.entry(fields.len()).or_insert(0) += 1;
+        if self.columns.len() < fields.len() {
+            self.columns.resize_with(fields.len(), Default::default);
+        }
+        for (col, field) in self.columns.iter_mut().zip(fields) {
+            let width = field.chars().count();
+            col.min_width = Some(col.min_width.map_or(width, |w| w.min(width)));
+            col.max_width = col.max_width.max(width);
+            if !field.is_empty() {
+                col.non_empty += 1;
+            }
+            col.distinct.insert(field);
+        }
+    }
+    pub(super) fn make_out_s(&self, conf: &CmdOptConf) -> anyhow::Result<Vec<String>> {
+        let loc = &conf.opt_locale;
+        let mut vec: Vec<String> = Vec::new();
+        vec.push(format!(
+            "fields: rows: {}",
+            loc.formatted_string(self.row_count)
+        ));
+        let consistent = if self.field_counts.len() <= 1 {
+            "yes"
+        } else {
+            "no"
+        };
+        vec.push(format!("fields: consistent: {consistent}"));
+        for (n, rows) in self.field_counts.iter() {
+            vec.push(format!(
+                "fields: field-count: {}: {}",
+                n,
+                loc.formatted_string(*rows)
+            ));
+        }
+        for (i, col) in self.columns.iter().enumerate() {
+            vec.push(format!(
+                "fields: column: {}: non-empty: {}, distinct: {}, width: {}..{}",
+                i + 1,
+                loc.formatted_string(col.non_empty),
+                loc.formatted_string(col.distinct.len() as u64),
+                col.min_width.unwrap_or(0),
+                col.max_width,
+            ));
+        }
+        Ok(vec)
+    }
+    pub(super) fn make_out_s_numeric(&self, conf: &CmdOptConf) -> anyhow::Result<Vec<String>> {
+        self.numeric.make_out_s(conf)
+    }
+}
+
+impl StatsNumeric {
+    fn count_up(&mut self, fields: &[String]) {
+        let idx = match self.field_idx {
+            Some(idx) => idx,
+            None => return,
+        };
+        match fields.get(idx).and_then(|s| s.trim().parse::<f64>().ok()) {
+            Some(v) if v.is_finite() => self.values.push(v),
+            _ => self.skipped += 1,
+        }
+    }
+    fn make_out_s(&self, conf: &CmdOptConf) -> anyhow::Result<Vec<String>> {
+        let loc = &conf.opt_locale;
+        let mut vec: Vec<String> = Vec::new();
+        vec.push(format!(
+            "numeric: count: {}",
+            loc.formatted_string(self.values.len() as u64)
+        ));
+        vec.push(format!(
+            "numeric: skipped: {}",
+            loc.formatted_string(self.skipped)
+        ));
+        if self.values.is_empty() {
+            return Ok(vec);
+        }
+        let mut sorted = self.values.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = sorted.len() as f64;
+        let sum: f64 = sorted.iter().sum();
+        let mean = sum / n;
+        // the population standard deviation
+        let var = sorted.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / n;
+        let items = [
+            ("sum", sum),
+            ("min", sorted[0]),
+            ("max", sorted[sorted.len() - 1]),
+            ("mean", mean),
+            ("stddev", var.sqrt()),
+            ("p50", percentile(&sorted, 50.0)),
+            ("p90", percentile(&sorted, 90.0)),
+            ("p99", percentile(&sorted, 99.0)),
+        ];
+        for (label, v) in items {
+            vec.push(format!("numeric: {label}: {}", loc.formatted_f64(v)));
+        }
+        Ok(vec)
+    }
+}
+
+// the linear interpolation between the closest ranks of the sorted values.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+// splits the record into the fields, aware of the quoted csv fields.
+// returns None if the record ends in the quoted field.
+fn split_fields(record: &str, delimiter: char) -> Option<Vec<String>> {
+    let (fields, in_quote) = split_fields_0(record, delimiter);
+    if in_quote {
+        None
+    } else {
+        Some(fields)
+    }
+}
+
+fn split_fields_lenient(record: &str, delimiter: char) -> Vec<String> {
+    split_fields_0(record, delimiter).0
+}
+
+fn split_fields_0(record: &str, delimiter: char) -> (Vec<String>, bool) {
+    let mut fields: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quote = false;
+    let mut at_start = true;
+    let mut chars = record.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quote {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    // the escaped quote
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quote = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == delimiter {
+            fields.push(std::mem::take(&mut field));
+            at_start = true;
+            continue;
+        } else if c == '"' && at_start {
+            in_quote = true;
+        } else {
+            field.push(c);
+        }
+        at_start = false;
+    }
+    fields.push(field);
+    (fields, in_quote)
+}
@@ -0,0 +1,53 @@
+use super::walk::InputFile;
+use crate::util::OptGroupBy;
+use std::path::{Component, Path, PathBuf};
+
+// the subtotal group of the file path of --group-by.
+pub(super) fn group_key(group_by: OptGroupBy, file: &InputFile) -> String {
+    let path = Path::new(&file.path);
+    match group_by {
+        OptGroupBy::Ext => match path.extension() {
+            Some(ext) => format!(".{}", ext.to_string_lossy()),
+            None => "(none)".to_string(),
+        },
+        OptGroupBy::Dir => dir_key(path, usize::MAX),
+        // the depth is counted from the root directory of -r,
+        // or from the beginning of the path as given.
+        OptGroupBy::Depth(depth) => {
+            let root_depth = match file.root {
+                Some(ref root) if *root != file.path => counted_components(Path::new(root)).count(),
+                _ => 0,
+            };
+            dir_key(path, root_depth.saturating_add(depth))
+        }
+    }
+}
+
+// the components of the depth, without the root directory and the prefix of windows.
+fn counted_components(path: &Path) -> impl Iterator<Item = Component<'_>> {
+    path.components()
+        .filter(|c| matches!(c, Component::Normal(_) | Component::ParentDir))
+}
+
+fn dir_key(path: &Path, depth: usize) -> String {
+    let mut dir = PathBuf::new();
+    let mut count = 0;
+    for c in path.parent().into_iter().flat_map(Path::components) {
+        match c {
+            Component::CurDir => {}
+            Component::Prefix(_) | Component::RootDir => dir.push(c),
+            Component::Normal(_) | Component::ParentDir => {
+                if count == depth {
+                    break;
+                }
+                dir.push(c);
+                count += 1;
+            }
+        }
+    }
+    if dir.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        dir.to_string_lossy().into_owned()
+    }
+}
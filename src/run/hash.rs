@@ -0,0 +1,87 @@
+use crate::util::OptHashAlg;
+use sha2::Digest;
+use std::io::{BufRead, Read};
+
+enum ContentHash {
+    Sha256(sha2::Sha256),
+    Blake3(Box<blake3::Hasher>),
+    Crc32(crc32fast::Hasher),
+    Xxh3(Box<xxhash_rust::xxh3::Xxh3>),
+}
+
+impl ContentHash {
+    fn new(alg: OptHashAlg) -> Self {
+        match alg {
+            OptHashAlg::Sha256 => ContentHash::Sha256(sha2::Sha256::new()),
+            OptHashAlg::Blake3 => ContentHash::Blake3(Box::new(blake3::Hasher::new())),
+            OptHashAlg::Crc32 => ContentHash::Crc32(crc32fast::Hasher::new()),
+            OptHashAlg::Xxh3 => ContentHash::Xxh3(Box::new(xxhash_rust::xxh3::Xxh3::new())),
+        }
+    }
+    fn update(&mut self, buf: &[u8]) {
+        match self {
+            ContentHash::Sha256(h) => h.update(buf),
+            ContentHash::Blake3(h) => {
+                h.update(buf);
+            }
+            ContentHash::Crc32(h) => h.update(buf),
+            ContentHash::Xxh3(h) => h.update(buf),
+        }
+    }
+    fn finish(self) -> String {
+        match self {
+            ContentHash::Sha256(h) => to_hex(&h.finalize()),
+            ContentHash::Blake3(h) => h.finalize().to_hex().to_string(),
+            ContentHash::Crc32(h) => format!("{:08x}", h.finalize()),
+            ContentHash::Xxh3(h) => format!("{:016x}", h.digest()),
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// the reader that hashes the exact bytes passing through it,
+// so that the digest is computed in the same pass as the statistics.
+pub(super) struct HashBufRead<'a> {
+    inner: Box<dyn BufRead + 'a>,
+    hash: Option<ContentHash>,
+}
+
+impl<'a> HashBufRead<'a> {
+    pub(super) fn new(inner: Box<dyn BufRead + 'a>, alg: Option<OptHashAlg>) -> Self {
+        Self {
+            inner,
+            hash: alg.map(ContentHash::new),
+        }
+    }
+    pub(super) fn finish(self) -> Option<String> {
+        self.hash.map(ContentHash::finish)
+    }
+}
+
+impl Read for HashBufRead<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if let Some(ref mut hash) = self.hash {
+            hash.update(&buf[..n]);
+        }
+        Ok(n)
+    }
+}
+
+impl BufRead for HashBufRead<'_> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+    fn consume(&mut self, amt: usize) {
+        if let Some(ref mut hash) = self.hash {
+            // the buffered bytes are returned again without reading.
+            if let Ok(buf) = self.inner.fill_buf() {
+                hash.update(&buf[..amt]);
+            }
+        }
+        self.inner.consume(amt);
+    }
+}
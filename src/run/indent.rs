@@ -0,0 +1,86 @@
+use crate::conf::CmdOptConf;
+use std::collections::BTreeMap;
+
+// the candidates of the indentation unit, from the widest.
+const INDENT_UNITS: [usize; 3] = [8, 4, 2];
+// the ratio of space-indented lines that must fit the unit.
+const INDENT_UNIT_RATIO: f64 = 0.9;
+
+#[derive(Default)]
+pub(super) struct StatsIndent {
+    tab_count: u64,
+    space_count: u64,
+    mixed_count: u64,
+    // leading space width -> line count
+    widths: BTreeMap<usize, u64>,
+}
+
+impl StatsIndent {
+    pub(super) fn count_up(&mut self, line_ss: &str) {
+        let bytes = line_ss.as_bytes();
+        let lead_len = bytes
+            .iter()
+            .position(|&b| b != b' ' && b != b'\t')
+            .unwrap_or(bytes.len());
+        if lead_len == 0 || lead_len == bytes.len() {
+            // no indentation or whitespace-only line
+            return;
+        }
+        let lead = &bytes[..lead_len];
+        let has_tab = lead.contains(&b'\t');
+        let has_space = lead.contains(&b' ');
+        match (has_tab, has_space) {
+            (true, true) => self.mixed_count += 1,
+            (true, false) => self.tab_count += 1,
+            _ => {
+                self.space_count += 1;
+                *self.widths.entry(lead_len).or_insert(0) += 1;
+            }
+        }
+    }
+    pub(super) fn unit(&self) -> Option<usize> {
+        if self.space_count == 0 {
+            return None;
+        }
+        for unit in INDENT_UNITS {
+            let fit: u64 = self
+                .widths
+                .iter()
+                .filter(|(w, _)| *w % unit == 0)
+                .map(|(_, c)| c)
+                .sum();
+            if fit as f64 >= self.space_count as f64 * INDENT_UNIT_RATIO {
+                return Some(unit);
+            }
+        }
+        None
+    }
+    pub(super) fn make_out_s(&self, conf: &CmdOptConf) -> anyhow::Result<Vec<String>> {
+        let loc = &conf.opt_locale;
+        let mut vec: Vec<String> = Vec::new();
+        vec.push(format!(
+            "indent: tab: {}",
+            loc.formatted_string(self.tab_count)
+        ));
+        vec.push(format!(
+            "indent: space: {}",
+            loc.formatted_string(self.space_count)
+        ));
+        vec.push(format!(
+            "indent: mixed: {}",
+            loc.formatted_string(self.mixed_count)
+        ));
+        match self.unit() {
+            Some(unit) => vec.push(format!("indent: unit: {unit}")),
+            None => vec.push("indent: unit: -".to_string()),
+        }
+        for (width, count) in self.widths.iter() {
+            vec.push(format!(
+                "indent: width: {}: {}",
+                width,
+                loc.formatted_string(*count)
+            ));
+        }
+        Ok(vec)
+    }
+}
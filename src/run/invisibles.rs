@@ -0,0 +1,110 @@
+use super::map_ascii::ASCII_CTRL_NAMES;
+use crate::conf::CmdOptConf;
+use std::collections::BTreeMap;
+
+#[derive(Default)]
+struct InvisibleRec {
+    count: u64,
+    // (line, column), 1-origin
+    locs: Vec<(u64, u64)>,
+}
+
+#[derive(Default)]
+pub(super) struct StatsInvisibles {
+    max_locs: usize,
+    total: u64,
+    map: BTreeMap<char, InvisibleRec>,
+}
+
+impl StatsInvisibles {
+    pub(super) fn new(max_locs: usize) -> Self {
+        Self {
+            max_locs,
+            ..Default::default()
+        }
+    }
+    pub(super) fn count_up(&mut self, line_num: u64, line_ss: &str) {
+        if line_ss
+            .bytes()
+            .all(|b| (0x20..0x7F).contains(&b) || b == b'\t')
+        {
+            return;
+        }
+        for (i, c) in line_ss.chars().enumerate() {
+            if line_num == 1 && i == 0 && c == '\u{FEFF}' {
+                // the BOM at the beginning of the text is not invisible.
+                continue;
+            }
+            if invisible_name(c).is_none() {
+                continue;
+            }
+            self.total += 1;
+            let rec = self.map.entry(c).or_default();
+            rec.count += 1;
+            if rec.locs.len() < self.max_locs {
+                rec.locs.push((line_num, i as u64 + 1));
+            }
+        }
+    }
+    pub(super) fn make_out_s(&self, conf: &CmdOptConf) -> anyhow::Result<Vec<String>> {
+        let loc = &conf.opt_locale;
+        let mut vec: Vec<String> = Vec::new();
+        vec.push(format!(
+            "invisible: total: {}",
+            loc.formatted_string(self.total)
+        ));
+        for (c, rec) in self.map.iter() {
+            let name = invisible_name(*c).unwrap_or("");
+            let mut s = format!(
+                "invisible: U+{:04X} {}: {}",
+                *c as u32,
+                name,
+                loc.formatted_string(rec.count)
+            );
+            if !rec.locs.is_empty() {
+                let locs: Vec<String> = rec
+                    .locs
+                    .iter()
+                    .map(|(line, col)| format!("{line}:{col}"))
+                    .collect();
+                s.push_str(": at ");
+                s.push_str(&locs.join(", "));
+            }
+            vec.push(s);
+        }
+        Ok(vec)
+    }
+}
+
+// the name of the control or invisible character, or None if it is visible.
+fn invisible_name(c: char) -> Option<&'static str> {
+    let name = match c {
+        // HT and LF are the usual white spaces.
+        '\t' | '\n' => return None,
+        '\u{00}'..='\u{1F}' => ASCII_CTRL_NAMES[c as usize],
+        '\u{7F}' => "DEL",
+        '\u{80}'..='\u{9F}' => "C1",
+        '\u{A0}' => "NBSP",
+        '\u{AD}' => "SHY",
+        '\u{061C}' => "ALM",
+        '\u{200B}' => "ZWSP",
+        '\u{200C}' => "ZWNJ",
+        '\u{200D}' => "ZWJ",
+        '\u{200E}' => "LRM",
+        '\u{200F}' => "RLM",
+        '\u{202A}' => "LRE",
+        '\u{202B}' => "RLE",
+        '\u{202C}' => "PDF",
+        '\u{202D}' => "LRO",
+        '\u{202E}' => "RLO",
+        '\u{202F}' => "NNBSP",
+        '\u{2060}' => "WJ",
+        '\u{2066}' => "LRI",
+        '\u{2067}' => "RLI",
+        '\u{2068}' => "FSI",
+        '\u{2069}' => "PDI",
+        '\u{FEFF}' => "BOM",
+        _ => return None,
+    };
+    Some(name)
+}
@@ -0,0 +1,129 @@
+use crate::conf::CmdOptConf;
+use crate::util::OptLogBucket;
+use regex::Regex;
+use std::collections::BTreeMap;
+
+const LOG_LEVELS: [&str; 4] = ["ERROR", "WARN", "INFO", "DEBUG"];
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+pub(super) struct StatsLog {
+    bucket: OptLogBucket,
+    level_counts: [u64; 4],
+    other_count: u64,
+    first: Option<String>,
+    last: Option<String>,
+    // the bucket of the normalised timestamp -> line count
+    buckets: BTreeMap<String, u64>,
+    re_level: Regex,
+    re_iso: Regex,
+    re_clf: Regex,
+    re_syslog: Regex,
+}
+
+impl StatsLog {
+    pub(super) fn new(bucket: OptLogBucket) -> Self {
+        Self {
+            bucket,
+            level_counts: [0; 4],
+            other_count: 0,
+            first: None,
+            last: None,
+            buckets: BTreeMap::new(),
+            re_level: Regex::new(r"\b(ERROR|WARN|WARNING|INFO|DEBUG)\b").unwrap(),
+            // 2024-01-02T03:04:05 or 2024-01-02 03:04:05
+            re_iso: Regex::new(r"\b(\d{4})-(\d{2})-(\d{2})[T ](\d{2}):(\d{2}):(\d{2})").unwrap(),
+            // [02/Jan/2024:03:04:05 +0000]
+            re_clf: Regex::new(r"\b(\d{2})/([A-Z][a-z]{2})/(\d{4}):(\d{2}):(\d{2}):(\d{2})")
+                .unwrap(),
+            // Jan  2 03:04:05, the year is unknown
+            re_syslog: Regex::new(r"\b([A-Z][a-z]{2}) +(\d{1,2}) (\d{2}):(\d{2}):(\d{2})\b")
+                .unwrap(),
+        }
+    }
+    pub(super) fn count_up(&mut self, line_ss: &str) {
+        match self.re_level.captures(line_ss) {
+            Some(cap) => {
+                let level = match &cap[1] {
+                    "WARNING" => "WARN",
+                    s => s,
+                };
+                let idx = LOG_LEVELS.iter().position(|&x| x == level).unwrap();
+                self.level_counts[idx] += 1;
+            }
+            None => self.other_count += 1,
+        }
+        if let Some(ts) = self.timestamp(line_ss) {
+            // "YYYY-MM-DD HH:MM:SS" or "MM-DD HH:MM:SS"
+            let len = ts.len()
+                - match self.bucket {
+                    OptLogBucket::Hour => 6,
+                    OptLogBucket::Minute => 3,
+                };
+            *self.buckets.entry(ts[..len].to_string()).or_insert(0) += 1;
+            if self.first.is_none() {
+                self.first = Some(ts.clone());
+            }
+            self.last = Some(ts);
+        }
+    }
+    // the normalised timestamp of the line
+    fn timestamp(&self, line_ss: &str) -> Option<String> {
+        if let Some(cap) = self.re_iso.captures(line_ss) {
+            return Some(format!(
+                "{}-{}-{} {}:{}:{}",
+                &cap[1], &cap[2], &cap[3], &cap[4], &cap[5], &cap[6]
+            ));
+        }
+        if let Some(cap) = self.re_clf.captures(line_ss) {
+            let month = month_num(&cap[2])?;
+            return Some(format!(
+                "{}-{:02}-{} {}:{}:{}",
+                &cap[3], month, &cap[1], &cap[4], &cap[5], &cap[6]
+            ));
+        }
+        if let Some(cap) = self.re_syslog.captures(line_ss) {
+            let month = month_num(&cap[1])?;
+            let day: u32 = cap[2].parse().ok()?;
+            return Some(format!(
+                "{:02}-{:02} {}:{}:{}",
+                month, day, &cap[3], &cap[4], &cap[5]
+            ));
+        }
+        None
+    }
+    pub(super) fn make_out_s(&self, conf: &CmdOptConf) -> anyhow::Result<Vec<String>> {
+        let loc = &conf.opt_locale;
+        let mut vec: Vec<String> = Vec::new();
+        for (level, count) in LOG_LEVELS.iter().zip(self.level_counts.iter()) {
+            vec.push(format!("log: {level}: {}", loc.formatted_string(*count)));
+        }
+        vec.push(format!(
+            "log: other: {}",
+            loc.formatted_string(self.other_count)
+        ));
+        vec.push(format!(
+            "log: first: {}",
+            self.first.as_deref().unwrap_or("-")
+        ));
+        vec.push(format!(
+            "log: last: {}",
+            self.last.as_deref().unwrap_or("-")
+        ));
+        for (key, count) in self.buckets.iter() {
+            vec.push(format!(
+                "log: per-{}: {}: {}",
+                self.bucket,
+                key,
+                loc.formatted_string(*count)
+            ));
+        }
+        Ok(vec)
+    }
+}
+
+fn month_num(s: &str) -> Option<usize> {
+    MONTHS.iter().position(|&m| m == s).map(|i| i + 1)
+}
@@ -0,0 +1,288 @@
+use crate::conf::CmdOptConf;
+use crate::util::{OptMapAsciiMode, OptMapSort, OptTableLang, OptTableNorm, OptTableType};
+
+// the mnemonics of the ascii control codes: 0x00 ..= 0x1F
+#[rustfmt::skip]
+pub(super) const ASCII_CTRL_NAMES: [&str; 32] = [
+    "NUL", "SOH", "STX", "ETX", "EOT", "ENQ", "ACK", "BEL",
+    "BS",  "HT",  "LF",  "VT",  "FF",  "CR",  "SO",  "SI",
+    "DLE", "DC1", "DC2", "DC3", "DC4", "NAK", "SYN", "ETB",
+    "CAN", "EM",  "SUB", "ESC", "FS",  "GS",  "RS",  "US",
+];
+
+pub(super) struct StatsAscii {
+    ascii: Vec<u64>,
+    // the newlines or the record separators, that end the records.
+    ends: Vec<u64>,
+}
+impl StatsAscii {
+    pub(super) fn new(sz: usize) -> StatsAscii {
+        Self {
+            ascii: vec![0; sz],
+            ends: vec![0; sz],
+        }
+    }
+    pub(super) fn is_enabled(&self) -> bool {
+        !self.ascii.is_empty()
+    }
+    pub(super) fn count_up(&mut self, b: u8) {
+        if b < 128 {
+            self.ascii[b as usize] += 1;
+        }
+    }
+    pub(super) fn count_up_end(&mut self, b: u8) {
+        if b < 128 {
+            self.ends[b as usize] += 1;
+        }
+    }
+    fn get_count(&self, idx: usize) -> u64 {
+        if idx < 128 {
+            self.ascii[idx]
+        } else {
+            0
+        }
+    }
+    fn max(&self) -> u64 {
+        *self.ascii.iter().max().unwrap()
+    }
+    fn total(&self) -> u64 {
+        self.ascii.iter().sum()
+    }
+    // the counts with the bytes that end the records.
+    fn get_count_with_ends(&self, idx: usize) -> u64 {
+        if idx < 128 {
+            self.ascii[idx] + self.ends[idx]
+        } else {
+            0
+        }
+    }
+    fn total_with_ends(&self) -> u64 {
+        self.total() + self.ends.iter().sum::<u64>()
+    }
+}
+impl std::default::Default for StatsAscii {
+    fn default() -> Self {
+        Self {
+            ascii: Vec::with_capacity(0),
+            ends: Vec::with_capacity(0),
+        }
+    }
+}
+
+impl StatsAscii {
+    pub(super) fn make_out_s(&self, conf: &CmdOptConf) -> anyhow::Result<Vec<String>> {
+        let mut rows = match conf.opt_map_ascii {
+            OptMapAsciiMode::Detailed => self.make_rows_detailed(conf, false),
+            OptMapAsciiMode::Nonzero => self.make_rows_detailed(conf, true),
+            _ => self.make_rows_plain(conf),
+        };
+        if conf.opt_map_sort == OptMapSort::Count {
+            // the stable sort keeps the code order of the same counts.
+            rows.sort_by_key(|row| std::cmp::Reverse(row.count));
+        }
+        if conf.flg_map_bar {
+            add_bars(&mut rows, conf.map_width());
+        }
+        Ok(rows.into_iter().map(|row| row.text).collect())
+    }
+}
+
+impl StatsAscii {
+    pub(super) fn make_out_s_table(
+        &self,
+        conf: &CmdOptConf,
+        lang: OptTableLang,
+    ) -> anyhow::Result<String> {
+        let typ = conf.opt_table_type;
+        let vec = self.table_values(typ, conf.opt_table_norm);
+        let name = conf.table_name();
+        let sz = vec.len();
+        let vals: Vec<String> = vec.iter().map(|v| v.to_lang_string(lang)).collect();
+        let vals = vals.join(", ");
+        let s = match lang {
+            OptTableLang::Rust => format!("const {name}: [{typ};{sz}] = [{vals}];"),
+            OptTableLang::C => {
+                let c_typ = match typ {
+                    OptTableType::U8 => "uint8_t",
+                    OptTableType::U16 => "uint16_t",
+                    OptTableType::F32 => "float",
+                };
+                format!("const {c_typ} {name}[{sz}] = {{{vals}}};")
+            }
+            OptTableLang::Python => format!("{name} = [{vals}]"),
+            OptTableLang::Json => format!("{{\"{name}\": [{vals}]}}"),
+        };
+        Ok(s)
+    }
+    fn table_values(&self, typ: OptTableType, norm: OptTableNorm) -> Vec<TableValue> {
+        let denom = match norm {
+            OptTableNorm::Max => self.max(),
+            OptTableNorm::Sum | OptTableNorm::Log => self.total(),
+        };
+        let mut vec: Vec<TableValue> = Vec::new();
+        for i in 0x00..0x80 {
+            let val = self.get_count(i);
+            // the empty input has the denominator 0, its table is all 0.
+            let v = match typ {
+                OptTableType::U8 | OptTableType::U16 => {
+                    let scale = if typ == OptTableType::U8 {
+                        u8::MAX as u64
+                    } else {
+                        u16::MAX as u64
+                    };
+                    TableValue::Int((val * scale).checked_div(denom).unwrap_or(0))
+                }
+                OptTableType::F32 => {
+                    let p = if denom > 0 {
+                        val as f64 / denom as f64
+                    } else {
+                        0.0
+                    };
+                    if norm == OptTableNorm::Log {
+                        TableValue::Float(p.ln() as f32)
+                    } else {
+                        TableValue::Float(p as f32)
+                    }
+                }
+            };
+            vec.push(v);
+        }
+        vec
+    }
+    fn make_rows_plain(&self, conf: &CmdOptConf) -> Vec<MapRow> {
+        let total = self.total();
+        let percent = conf.flg_map_percent;
+        let mut vec: Vec<MapRow> = Vec::new();
+        let mut ascii_ctrl: u64 = 0;
+        let mut ascii_ctrl_ht: u64 = 0;
+        let mut ascii_ctrl_vt: u64 = 0;
+        //let mut ascii_ctrl_lf: u64 = 0;
+        //let mut ascii_ctrl_cr: u64 = 0;
+        for i in 0..0x20 {
+            let val = self.get_count(i);
+            match i {
+                0x09 => ascii_ctrl_ht = val,
+                0x0B => ascii_ctrl_vt = val,
+                //0x0A => ascii_ctrl_lf = val,
+                //0x0D => ascii_ctrl_cr = val,
+                _ => ascii_ctrl += val,
+            }
+        }
+        ascii_ctrl += self.get_count(0x7F);
+        vec.push(MapRow::new("ctrl: --", ascii_ctrl, percent, total));
+        //vec.push(MapRow::new("ctrl: lf", ascii_ctrl_lf, percent, total));
+        //vec.push(MapRow::new("ctrl: cr", ascii_ctrl_cr, percent, total));
+        vec.push(MapRow::new("ctrl: ht", ascii_ctrl_ht, percent, total));
+        vec.push(MapRow::new("ctrl: vt", ascii_ctrl_vt, percent, total));
+        //
+        vec.push(MapRow::new(
+            "0x20: SP",
+            self.get_count(0x20),
+            percent,
+            total,
+        ));
+        for i in 0x21..0x7F {
+            let label = format!("0x{:02x}:  {}", i, i as u8 as char);
+            vec.push(MapRow::new(&label, self.get_count(i), percent, total));
+        }
+        vec
+    }
+    fn make_rows_detailed(&self, conf: &CmdOptConf, nonzero: bool) -> Vec<MapRow> {
+        let loc = &conf.opt_locale;
+        let total = self.total_with_ends();
+        let mut vec: Vec<MapRow> = Vec::new();
+        for i in 0x00..0x80 {
+            let val = self.get_count_with_ends(i);
+            if nonzero && val == 0 {
+                continue;
+            }
+            let text = format!(
+                "0x{:02x}: {:>3}: {}: {}",
+                i,
+                ascii_name(i as u8),
+                loc.formatted_string(val),
+                percent_string(val, total)
+            );
+            vec.push(MapRow { text, count: val });
+        }
+        vec
+    }
+}
+
+struct MapRow {
+    text: String,
+    count: u64,
+}
+impl MapRow {
+    fn new(label: &str, count: u64, percent: bool, total: u64) -> MapRow {
+        let text = if percent {
+            format!("{label}: {count}: {}", percent_string(count, total))
+        } else {
+            format!("{label}: {count}")
+        };
+        Self { text, count }
+    }
+}
+
+fn percent_string(val: u64, total: u64) -> String {
+    let percent = if total > 0 {
+        val as f64 * 100.0 / total as f64
+    } else {
+        0.0
+    };
+    format!("{percent:.2}%")
+}
+
+// the bars are scaled to the largest count, in the room left by the widest row.
+fn add_bars(rows: &mut [MapRow], width: usize) {
+    const BAR_MIN_WIDTH: usize = 10;
+    let max_count = rows.iter().map(|row| row.count).max().unwrap_or(0);
+    let text_len = rows
+        .iter()
+        .map(|row| row.text.chars().count())
+        .max()
+        .unwrap_or(0);
+    let bar_width = width.saturating_sub(text_len + 2).max(BAR_MIN_WIDTH);
+    for row in rows.iter_mut() {
+        let len = (row.count as f64 * bar_width as f64 / max_count.max(1) as f64).round() as usize;
+        if len == 0 {
+            continue;
+        }
+        let pad = text_len - row.text.chars().count();
+        row.text = format!(
+            "{}{}: {}",
+            row.text,
+            " ".repeat(pad),
+            "\u{2588}".repeat(len)
+        );
+    }
+}
+
+fn ascii_name(b: u8) -> String {
+    match b {
+        0x00..=0x1F => ASCII_CTRL_NAMES[b as usize].to_string(),
+        0x20 => "SP".to_string(),
+        0x7F => "DEL".to_string(),
+        _ => (b as char).to_string(),
+    }
+}
+
+enum TableValue {
+    Int(u64),
+    Float(f32),
+}
+impl TableValue {
+    fn to_lang_string(&self, lang: OptTableLang) -> String {
+        match *self {
+            TableValue::Int(v) => format!("{v}"),
+            TableValue::Float(v) if v.is_finite() => format!("{v:?}"),
+            // the log-probability of 0
+            TableValue::Float(_) => match lang {
+                OptTableLang::Rust => "f32::NEG_INFINITY".to_string(),
+                OptTableLang::C => "-INFINITY".to_string(),
+                OptTableLang::Python => "float(\"-inf\")".to_string(),
+                OptTableLang::Json => "null".to_string(),
+            },
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::conf::CmdOptConf;
+use crate::stats::WordDelims;
+use regex::Regex;
+
+pub(super) struct StatsMarkdown {
+    headings: [u64; 6],
+    code_blocks: u64,
+    code_lines: u64,
+    links: u64,
+    images: u64,
+    list_items: u64,
+    words: u64,
+    // the fence char and its length, in the code block
+    fence: Option<(char, usize)>,
+    prev_is_paragraph: bool,
+    re_link: Regex,
+    re_list: Regex,
+}
+
+impl StatsMarkdown {
+    pub(super) fn new() -> Self {
+        Self {
+            headings: [0; 6],
+            code_blocks: 0,
+            code_lines: 0,
+            links: 0,
+            images: 0,
+            list_items: 0,
+            words: 0,
+            fence: None,
+            prev_is_paragraph: false,
+            re_link: Regex::new(r"(!?)\[([^\]]*)\]\([^)]*\)").unwrap(),
+            re_list: Regex::new(r"^\s*([-*+]|[0-9]+[.)])\s+").unwrap(),
+        }
+    }
+    pub(super) fn count_up(&mut self, line_ss: &str, word_delims: &WordDelims) {
+        let fence = fence_of(line_ss);
+        if let Some((fc, flen)) = self.fence {
+            match fence {
+                Some((c, len)) if c == fc && len >= flen && is_fence_end(line_ss) => {
+                    self.fence = None;
+                }
+                _ => self.code_lines += 1,
+            }
+            return;
+        }
+        if fence.is_some() {
+            self.fence = fence;
+            self.code_blocks += 1;
+            self.prev_is_paragraph = false;
+            return;
+        }
+        let trimmed = line_ss.trim();
+        if trimmed.is_empty() {
+            self.prev_is_paragraph = false;
+            return;
+        }
+        // the setext heading underlines the previous paragraph line.
+        if self.prev_is_paragraph && line_ss.len() - line_ss.trim_start().len() < 4 {
+            if trimmed.chars().all(|c| c == '=') {
+                self.headings[0] += 1;
+                self.prev_is_paragraph = false;
+                return;
+            }
+            if trimmed.chars().all(|c| c == '-') {
+                self.headings[1] += 1;
+                self.prev_is_paragraph = false;
+                return;
+            }
+        }
+        let mut text = trimmed.trim_start_matches('>').trim_start();
+        self.prev_is_paragraph = true;
+        let level = text.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&level) && text[level..].chars().next().is_none_or(char::is_whitespace)
+        {
+            self.headings[level - 1] += 1;
+            text = &text[level..];
+            self.prev_is_paragraph = false;
+        } else if let Some(m) = self.re_list.find(text) {
+            self.list_items += 1;
+            text = &text[m.end()..];
+        }
+        // the links are counted as their texts, the images are not words.
+        for cap in self.re_link.captures_iter(text) {
+            if cap[1].is_empty() {
+                self.links += 1;
+            } else {
+                self.images += 1;
+            }
+        }
+        let prose = self.re_link.replace_all(text, |cap: &regex::Captures| {
+            if cap[1].is_empty() {
+                cap[2].to_string()
+            } else {
+                String::new()
+            }
+        });
+        let mut prev_is_delim = true;
+        for c in prose.chars() {
+            let is_delim = word_delims.is_delim(c);
+            if prev_is_delim && !is_delim {
+                self.words += 1;
+            }
+            prev_is_delim = is_delim;
+        }
+    }
+    pub(super) fn make_out_s(&self, conf: &CmdOptConf) -> anyhow::Result<Vec<String>> {
+        let loc = &conf.opt_locale;
+        let mut vec: Vec<String> = Vec::new();
+        for (i, count) in self.headings.iter().enumerate() {
+            vec.push(format!(
+                "markdown: heading: h{}: {}",
+                i + 1,
+                loc.formatted_string(*count)
+            ));
+        }
+        let items = [
+            ("code-blocks", self.code_blocks),
+            ("code-lines", self.code_lines),
+            ("links", self.links),
+            ("images", self.images),
+            ("list-items", self.list_items),
+            ("words", self.words),
+        ];
+        for (label, count) in items {
+            vec.push(format!(
+                "markdown: {label}: {}",
+                loc.formatted_string(count)
+            ));
+        }
+        Ok(vec)
+    }
+}
+
+// the code fence: 3 or more backticks or tildes, indented less than 4 spaces.
+fn fence_of(line_ss: &str) -> Option<(char, usize)> {
+    let s = line_ss.trim_start_matches(' ');
+    if line_ss.len() - s.len() >= 4 {
+        return None;
+    }
+    let c = s.chars().next()?;
+    if c != '`' && c != '~' {
+        return None;
+    }
+    let len = s.chars().take_while(|&x| x == c).count();
+    if len >= 3 {
+        Some((c, len))
+    } else {
+        None
+    }
+}
+
+// the closing fence has no info string.
+fn is_fence_end(line_ss: &str) -> bool {
+    let s = line_ss.trim();
+    s.chars().all(|c| c == '`' || c == '~')
+}
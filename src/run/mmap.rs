@@ -0,0 +1,68 @@
+use crate::stats::{compute_bytes, Stats, StatsConfig};
+use memmap2::Mmap;
+use std::fs::File;
+
+// the input bytes of the regular file, mapped into memory.
+pub(super) struct MappedFile {
+    mmap: Option<Mmap>,
+}
+
+impl MappedFile {
+    pub(super) fn open(file: &File) -> anyhow::Result<Self> {
+        // the empty file can not be mapped on some platforms.
+        if file.metadata()?.len() == 0 {
+            return Ok(Self { mmap: None });
+        }
+        // SAFETY: the file is only read. it must not be truncated by
+        // an other process while it is counted.
+        let mmap = unsafe { Mmap::map(file)? };
+        Ok(Self { mmap: Some(mmap) })
+    }
+    pub(super) fn as_bytes(&self) -> &[u8] {
+        self.mmap.as_deref().unwrap_or(&[])
+    }
+}
+
+// the bytes are split into the chunks at newline boundaries,
+// like `threads::count_file_chunks()`, without reading the file again.
+pub(super) fn count_bytes_chunks(
+    bytes: &[u8],
+    config: &StatsConfig,
+    threads: usize,
+) -> anyhow::Result<Stats> {
+    let chunk_count =
+        (bytes.len() / super::threads::CHUNK_MIN_BYTES as usize).clamp(1, threads.max(1));
+    if chunk_count == 1 {
+        return compute_bytes(bytes, config);
+    }
+    let mut chunks = Vec::with_capacity(chunk_count);
+    let mut start = 0;
+    for k in 1..chunk_count {
+        let pos = (bytes.len() * k / chunk_count).max(start);
+        let bound = match memx::memchr(&bytes[pos..], b'\n') {
+            Some(n) => pos + n + 1,
+            None => bytes.len(),
+        };
+        chunks.push(&bytes[start..bound]);
+        start = bound;
+    }
+    chunks.push(&bytes[start..]);
+    let results: Vec<anyhow::Result<Stats>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| scope.spawn(move || compute_bytes(chunk, config)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| {
+                h.join()
+                    .unwrap_or_else(|_| Err(anyhow!("counting thread panicked")))
+            })
+            .collect()
+    });
+    let mut stats = Stats::default();
+    for r in results {
+        stats.merge(&r?);
+    }
+    Ok(stats)
+}
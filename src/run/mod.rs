@@ -0,0 +1,521 @@
+use crate::conf::CmdOptConf;
+use crate::stats::{for_each_record, Stats, StatsConfig};
+use crate::util::err::BrokenPipeError;
+use anyhow::Context;
+use runnel::RunnelIoe;
+use std::collections::BTreeMap;
+use std::fmt::Write as FmtWrite;
+use std::io::BufRead;
+
+mod code;
+use code::StatsCode;
+
+mod decompress;
+use decompress::decompress_reader;
+
+mod dup_lines;
+use dup_lines::StatsDupLines;
+
+mod fields;
+use fields::StatsFields;
+
+mod group;
+use group::group_key;
+
+mod hash;
+use hash::HashBufRead;
+
+mod indent;
+use indent::StatsIndent;
+
+mod invisibles;
+use invisibles::StatsInvisibles;
+
+mod log;
+use log::StatsLog;
+
+mod map_ascii;
+use map_ascii::StatsAscii;
+
+mod markdown;
+use markdown::StatsMarkdown;
+
+mod mmap;
+use mmap::{count_bytes_chunks, MappedFile};
+
+mod patterns;
+use patterns::StatsPatterns;
+
+mod threads;
+use threads::{count_file_chunks, for_each_parallel, threads_count};
+
+mod walk;
+use walk::{collect_files, is_binary, read_file_list, InputFile};
+
+pub fn run(sioe: &RunnelIoe, conf: &CmdOptConf) -> anyhow::Result<()> {
+    let r = run_0(sioe, conf);
+    if r.is_broken_pipe() {
+        return Ok(());
+    }
+    r
+}
+
+#[derive(Default)]
+struct StatsExtra {
+    code: Option<StatsCode>,
+    dup_lines: StatsDupLines,
+    fields: StatsFields,
+    // the statistics with the regexes are built only if enabled.
+    log: Option<StatsLog>,
+    map_ascii: StatsAscii,
+    markdown: Option<StatsMarkdown>,
+    indent: StatsIndent,
+    invisibles: StatsInvisibles,
+    patterns: StatsPatterns,
+    digest: Option<String>,
+    // the compressed and the decompressed byte counts of --decompress.
+    compressed: Option<(u64, u64)>,
+}
+
+impl StatsExtra {
+    // only the code and the pattern counts are merged into the total row,
+    // the others are of the each file.
+    fn merge(&mut self, other: &StatsExtra) {
+        if let Some(ref other_code) = other.code {
+            match self.code {
+                Some(ref mut code) => code.merge(other_code),
+                None => self.code = Some(other_code.clone()),
+            }
+        }
+        self.patterns.merge(&other.patterns);
+    }
+}
+
+fn stats_config(conf: &CmdOptConf) -> anyhow::Result<StatsConfig> {
+    let mut builder = StatsConfig::builder().word_delims(&conf.opt_word_delims);
+    if let Some(ref record_sep) = conf.opt_record_sep {
+        builder = builder.record_sep(record_sep);
+    }
+    if let Some(ref record_str) = conf.opt_record_str {
+        builder = builder.record_str(record_str);
+    }
+    let mut config = builder.build()?;
+    config.count_words = conf.flg_chars || conf.flg_words;
+    config.count_line_kinds = conf.flg_blank || conf.flg_ws_only || conf.flg_trailing_ws;
+    Ok(config)
+}
+
+// only the line statistics can be counted by the chunks of a file.
+fn is_chunkable(conf: &CmdOptConf) -> bool {
+    !conf.is_map_ascii()
+        && conf.table_lang().is_none()
+        && !conf.flg_indent
+        && !conf.flg_invisibles
+        && !conf.flg_dup_lines
+        && !conf.is_fields()
+        && conf.opt_code.is_none()
+        && !conf.flg_markdown
+        && !conf.flg_log
+        && conf.opt_pattern.is_empty()
+        && conf.opt_hash.is_none()
+        && conf.opt_record_sep.is_none()
+        && conf.opt_record_str.is_none()
+        && !conf.flg_decompress
+}
+
+fn run_0(sioe: &RunnelIoe, conf: &CmdOptConf) -> anyhow::Result<()> {
+    let config = stats_config(conf)?;
+    let is_file_list = conf.opt_files_from.is_some() || conf.opt_files0_from.is_some();
+    if conf.arg_params.is_empty() && !is_file_list {
+        let (_stats, _extra, out) = run_1(conf, &config, sioe.pg_in().lock_bufread(), None)?;
+        return write_out(sioe, out);
+    }
+    let threads = threads_count(conf.opt_threads);
+    if conf.arg_params.len() == 1
+        && !conf.flg_recursive
+        && !is_file_list
+        && conf.opt_group_by.is_none()
+    {
+        let path = &conf.arg_params[0];
+        let (_stats, _extra, out) = if path == "-" {
+            run_1(conf, &config, sioe.pg_in().lock_bufread(), Some(path))?
+        } else {
+            run_file(conf, &config, path, threads).with_context(|| path.to_string())?
+        };
+        return write_out(sioe, out);
+    }
+    let mut params = conf.arg_params.clone();
+    if let Some(ref path) = conf.opt_files_from {
+        let files = read_file_list(sioe, &conf.resolve_path(path), b'\n');
+        params.extend(files.with_context(|| path.to_string())?);
+    }
+    if let Some(ref path) = conf.opt_files0_from {
+        let files = read_file_list(sioe, &conf.resolve_path(path), b'\0');
+        params.extend(files.with_context(|| path.to_string())?);
+    }
+    let mut err_count = 0;
+    let files = if conf.flg_recursive {
+        let mut files = Vec::new();
+        for r in collect_files(conf, &params) {
+            match r {
+                Ok(file) => files.push(file),
+                Err(err) => {
+                    err_count += 1;
+                    write_err(sioe, format!("{}: {err:#}", conf.prog_name))?;
+                }
+            }
+        }
+        files
+    } else {
+        params
+            .into_iter()
+            .map(|path| InputFile { path, root: None })
+            .collect()
+    };
+    let file_count = err_count + files.len();
+    // the files are counted on the workers, one file per thread at a time.
+    let mut total = Stats::default();
+    let mut total_extra = StatsExtra::default();
+    let mut groups: BTreeMap<String, (Stats, StatsExtra)> = BTreeMap::new();
+    for_each_parallel(
+        &files,
+        threads,
+        |file| {
+            let path = file.path.as_str();
+            // the stdin of "-" is read in the order of the files, by `each`.
+            if path == "-" {
+                return Ok(None);
+            }
+            // the binary files found by -r are skipped.
+            if conf.flg_recursive && is_binary(&conf.resolve_path(path), conf.flg_decompress)? {
+                return Ok(None);
+            }
+            run_file(conf, &config, path, 1).map(Some)
+        },
+        |file, r| {
+            let path = file.path.as_str();
+            let r = if path == "-" {
+                run_1(conf, &config, sioe.pg_in().lock_bufread(), Some(path)).map(Some)
+            } else {
+                r
+            };
+            match r {
+                Ok(Some((stats, extra, out))) => {
+                    total.merge(&stats);
+                    total_extra.merge(&extra);
+                    if let Some(group_by) = conf.opt_group_by {
+                        let (group, group_extra) =
+                            groups.entry(group_key(group_by, file)).or_default();
+                        group.merge(&stats);
+                        group_extra.merge(&extra);
+                    }
+                    write_out(sioe, out)?;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    err_count += 1;
+                    write_err(sioe, format!("{}: {path}: {err:#}", conf.prog_name))?;
+                }
+            }
+            Ok(())
+        },
+    )?;
+    let mut out = Vec::new();
+    for (key, (stats, extra)) in &groups {
+        out.push(make_out_s_with_tag(conf, stats, extra, "group", key)?);
+    }
+    out.push(make_out_s_with_file(conf, &total, &total_extra, "total")?);
+    write_out(sioe, out)?;
+    if err_count > 0 {
+        return Err(anyhow!(
+            "{err_count} of {file_count} files could not be processed"
+        ));
+    }
+    Ok(())
+}
+
+fn write_err(sioe: &RunnelIoe, err_s: String) -> anyhow::Result<()> {
+    sioe.pg_err().write_line(err_s)?;
+    sioe.pg_err().flush_line()?;
+    Ok(())
+}
+
+fn write_out(sioe: &RunnelIoe, out: Vec<String>) -> anyhow::Result<()> {
+    if out.is_empty() {
+        return Ok(());
+    }
+    for v in out {
+        sioe.pg_out().write_line(v)?;
+    }
+    sioe.pg_out().flush_line()?;
+    Ok(())
+}
+
+fn run_file(
+    conf: &CmdOptConf,
+    config: &StatsConfig,
+    path: &str,
+    threads: usize,
+) -> anyhow::Result<(Stats, StatsExtra, Vec<String>)> {
+    // the file is opened by the path of -X base_dir=, and labeled as given.
+    let open_path = conf.resolve_path(path);
+    let is_file = std::fs::metadata(&open_path)?.is_file();
+    if conf.flg_mmap && is_file {
+        let file = std::fs::File::open(&open_path)?;
+        let mapped = MappedFile::open(&file)?;
+        if is_chunkable(conf) {
+            let stats = count_bytes_chunks(mapped.as_bytes(), config, threads)?;
+            let out_s = make_out_s_with_file(conf, &stats, &StatsExtra::default(), path)?;
+            return Ok((stats, StatsExtra::default(), vec![out_s]));
+        }
+        return run_1(conf, config, Box::new(mapped.as_bytes()), Some(path));
+    }
+    if threads > 1 && is_chunkable(conf) && is_file {
+        let stats = count_file_chunks(&open_path, config, threads)?;
+        let out_s = make_out_s_with_file(conf, &stats, &StatsExtra::default(), path)?;
+        return Ok((stats, StatsExtra::default(), vec![out_s]));
+    }
+    let file = std::fs::File::open(&open_path)?;
+    let reader = Box::new(std::io::BufReader::new(file));
+    run_1(conf, config, reader, Some(path))
+}
+
+fn run_1(
+    conf: &CmdOptConf,
+    config: &StatsConfig,
+    reader: Box<dyn BufRead + '_>,
+    file: Option<&str>,
+) -> anyhow::Result<(Stats, StatsExtra, Vec<String>)> {
+    let mut stats = Stats::default();
+    let mut extra = StatsExtra::default();
+    if conf.is_map_ascii() || conf.table_lang().is_some() {
+        extra.map_ascii = StatsAscii::new(128);
+    }
+    if conf.flg_invisibles {
+        extra.invisibles = StatsInvisibles::new(conf.opt_locations);
+    }
+    if conf.flg_markdown {
+        extra.markdown = Some(StatsMarkdown::new());
+    }
+    if let Some(lang) = conf.opt_code {
+        extra.code = StatsCode::new(lang, file);
+    }
+    if conf.flg_dup_lines {
+        extra.dup_lines = StatsDupLines::new(conf);
+    }
+    if conf.flg_log {
+        extra.log = Some(StatsLog::new(conf.opt_log_bucket));
+    }
+    if conf.is_fields() {
+        extra.fields = StatsFields::new(conf.field_delimiter(), conf.opt_numeric_field);
+    }
+    if !conf.opt_pattern.is_empty() {
+        extra.patterns = StatsPatterns::new(&conf.opt_pattern, conf.flg_fixed_strings)?;
+    }
+    // input
+    // the digest is of the input bytes, before the decompression.
+    let mut raw_reader = HashBufRead::new(reader, conf.opt_hash);
+    {
+        let (reader, sizes) = if conf.flg_decompress {
+            decompress_reader(Box::new(&mut raw_reader))?
+        } else {
+            (Box::new(&mut raw_reader) as Box<dyn BufRead>, None)
+        };
+        for_each_record(reader, config, |record_ss, end| {
+            run_00(conf, record_ss, end, config, &mut stats, &mut extra)
+        })?;
+        extra.compressed = sizes.map(|sizes| sizes.get());
+    }
+    if conf.opt_hash.is_some() {
+        // the bytes left after the compressed stream.
+        std::io::copy(&mut raw_reader, &mut std::io::sink())?;
+    }
+    extra.digest = raw_reader.finish();
+    if conf.is_fields() {
+        extra.fields.finish();
+    }
+    // output
+    let mut out = Vec::new();
+    {
+        let out_s = match file {
+            Some(path) => make_out_s_with_file(conf, &stats, &extra, path)?,
+            None => make_out_s_from_stats(conf, &stats, &extra)?,
+        };
+        if !out_s.is_empty() {
+            out.push(out_s);
+        }
+    }
+    if conf.flg_indent {
+        out.extend(extra.indent.make_out_s(conf)?);
+    }
+    if conf.flg_invisibles {
+        out.extend(extra.invisibles.make_out_s(conf)?);
+    }
+    if conf.flg_fields {
+        out.extend(extra.fields.make_out_s(conf)?);
+    }
+    if conf.opt_numeric_field.is_some() {
+        out.extend(extra.fields.make_out_s_numeric(conf)?);
+    }
+    if conf.flg_dup_lines {
+        out.extend(extra.dup_lines.make_out_s(conf)?);
+    }
+    if let Some(ref markdown) = extra.markdown {
+        out.extend(markdown.make_out_s(conf)?);
+    }
+    if let Some(ref log) = extra.log {
+        out.extend(log.make_out_s(conf)?);
+    }
+    if conf.is_map_ascii() && !conf.is_opt_uc_x_map_ascii_rust_src() {
+        out.extend(extra.map_ascii.make_out_s(conf)?);
+    }
+    if let Some(lang) = conf.table_lang() {
+        out.push(extra.map_ascii.make_out_s_table(conf, lang)?);
+    }
+    //
+    Ok((stats, extra, out))
+}
+
+fn run_00(
+    conf: &CmdOptConf,
+    line_ss: &str,
+    line_end: &[u8],
+    config: &StatsConfig,
+    stats: &mut Stats,
+    extra: &mut StatsExtra,
+) -> anyhow::Result<()> {
+    stats.count_up(line_ss, config);
+    if conf.flg_indent {
+        extra.indent.count_up(line_ss);
+    }
+    if conf.flg_invisibles {
+        extra.invisibles.count_up(stats.line_count, line_ss);
+    }
+    if let Some(ref mut code) = extra.code {
+        code.count_up(line_ss);
+    }
+    if conf.flg_dup_lines {
+        extra.dup_lines.count_up(line_ss);
+    }
+    if let Some(ref mut markdown) = extra.markdown {
+        markdown.count_up(line_ss, &config.word_delims);
+    }
+    if let Some(ref mut log) = extra.log {
+        log.count_up(line_ss);
+    }
+    if conf.is_fields() {
+        extra.fields.count_up(line_ss);
+    }
+    if !conf.opt_pattern.is_empty() {
+        extra.patterns.count_up(line_ss);
+    }
+    if extra.map_ascii.is_enabled() {
+        for b in line_ss.as_bytes() {
+            extra.map_ascii.count_up(*b);
+        }
+        for b in line_end {
+            extra.map_ascii.count_up_end(*b);
+        }
+    }
+    Ok(())
+}
+
+fn make_out_s_from_stats(
+    conf: &CmdOptConf,
+    stats: &Stats,
+    extra: &StatsExtra,
+) -> anyhow::Result<String> {
+    let mut vec: Vec<String> = Vec::new();
+    if conf.flg_lines {
+        vec.push(my_formatted(conf, "lines", stats.line_count)?);
+    }
+    if conf.flg_bytes {
+        vec.push(my_formatted(conf, "bytes", stats.byte_count)?);
+    }
+    if conf.flg_chars {
+        vec.push(my_formatted(conf, "chars", stats.char_count)?);
+    }
+    if conf.flg_words {
+        vec.push(my_formatted(conf, "words", stats.word_count)?);
+    }
+    if conf.flg_max_line_bytes {
+        vec.push(my_formatted(conf, "max", stats.max_line_bytes)?);
+    }
+    if conf.flg_blank {
+        vec.push(my_formatted(conf, "blank", stats.blank_count)?);
+    }
+    if conf.flg_ws_only {
+        vec.push(my_formatted(conf, "ws-only", stats.ws_only_count)?);
+    }
+    if conf.flg_trailing_ws {
+        vec.push(my_formatted(conf, "trailing-ws", stats.trailing_ws_count)?);
+    }
+    if let Some(ref code) = extra.code {
+        vec.push(my_formatted(conf, "code", code.code_count)?);
+        vec.push(my_formatted(conf, "comments", code.comment_count)?);
+        vec.push(my_formatted(conf, "blanks", code.blank_count)?);
+    } else if conf.opt_code.is_some() {
+        // the language of `--code auto` is not detected.
+        for label in ["code", "comments", "blanks"] {
+            vec.push(format!("{label}:\"-\""));
+        }
+    }
+    if let (Some(alg), Some(digest)) = (conf.opt_hash, &extra.digest) {
+        vec.push(format!("{alg}:\"{digest}\""));
+    }
+    if let (true, Some((compressed, decompressed))) = (conf.flg_compressed, extra.compressed) {
+        vec.push(my_formatted(conf, "compressed", compressed)?);
+        let ratio = if compressed == 0 {
+            0.0
+        } else {
+            (decompressed as f64 / compressed as f64 * 100.0).round() / 100.0
+        };
+        vec.push(format!(
+            "ratio:\"{}\"",
+            conf.opt_locale.formatted_f64(ratio)
+        ));
+    }
+    for (name, line_count, match_count) in extra.patterns.iter() {
+        vec.push(my_formatted(conf, &format!("matches:{name}"), match_count)?);
+        vec.push(my_formatted(
+            conf,
+            &format!("matched-lines:{name}"),
+            line_count,
+        )?);
+    }
+    Ok(vec.join(", ").to_string())
+}
+
+fn make_out_s_with_file(
+    conf: &CmdOptConf,
+    stats: &Stats,
+    extra: &StatsExtra,
+    path: &str,
+) -> anyhow::Result<String> {
+    make_out_s_with_tag(conf, stats, extra, "file", path)
+}
+
+fn make_out_s_with_tag(
+    conf: &CmdOptConf,
+    stats: &Stats,
+    extra: &StatsExtra,
+    tag: &str,
+    value: &str,
+) -> anyhow::Result<String> {
+    let out_s = make_out_s_from_stats(conf, stats, extra)?;
+    if out_s.is_empty() {
+        Ok(format!("{tag}:\"{value}\""))
+    } else {
+        Ok(format!("{out_s}, {tag}:\"{value}\""))
+    }
+}
+
+fn my_formatted(conf: &CmdOptConf, label: &str, num: u64) -> anyhow::Result<String> {
+    let mut s = String::new();
+    s.write_fmt(format_args!(
+        "{}:\"{}\"",
+        label,
+        conf.opt_locale.formatted_string(num)
+    ))?;
+    Ok(s)
+}
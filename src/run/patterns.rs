@@ -0,0 +1,59 @@
+use regex::Regex;
+
+#[derive(Clone)]
+struct PatternRec {
+    name: String,
+    re: Regex,
+    line_count: u64,
+    match_count: u64,
+}
+
+#[derive(Default, Clone)]
+pub(super) struct StatsPatterns {
+    recs: Vec<PatternRec>,
+}
+
+impl StatsPatterns {
+    pub(super) fn new(patterns: &[String], fixed_strings: bool) -> anyhow::Result<Self> {
+        let mut recs: Vec<PatternRec> = Vec::new();
+        for pat in patterns {
+            let re = if fixed_strings {
+                Regex::new(&regex::escape(pat))?
+            } else {
+                Regex::new(pat)?
+            };
+            recs.push(PatternRec {
+                name: pat.to_string(),
+                re,
+                line_count: 0,
+                match_count: 0,
+            });
+        }
+        Ok(Self { recs })
+    }
+    pub(super) fn count_up(&mut self, line_ss: &str) {
+        for rec in self.recs.iter_mut() {
+            let n = rec.re.find_iter(line_ss).count() as u64;
+            if n > 0 {
+                rec.line_count += 1;
+                rec.match_count += n;
+            }
+        }
+    }
+    pub(super) fn merge(&mut self, other: &Self) {
+        if self.recs.is_empty() {
+            self.recs = other.recs.clone();
+            return;
+        }
+        for (rec, other) in self.recs.iter_mut().zip(other.recs.iter()) {
+            rec.line_count += other.line_count;
+            rec.match_count += other.match_count;
+        }
+    }
+    // (name, matching line counts, occurrence counts)
+    pub(super) fn iter(&self) -> impl Iterator<Item = (&str, u64, u64)> {
+        self.recs
+            .iter()
+            .map(|rec| (rec.name.as_str(), rec.line_count, rec.match_count))
+    }
+}
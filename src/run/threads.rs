@@ -0,0 +1,135 @@
+use crate::stats::{compute, Stats, StatsConfig};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+// a chunk smaller than this is not worth a thread.
+pub(super) const CHUNK_MIN_BYTES: u64 = 64 * 1024;
+
+pub(crate) fn threads_count(opt_threads: usize) -> usize {
+    if opt_threads == 0 {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    } else {
+        opt_threads
+    }
+}
+
+// the file is split into the chunks at newline boundaries, so that every
+// chunk holds whole lines and the partial statistics merge exactly.
+pub(crate) fn count_file_chunks(
+    path: &str,
+    config: &StatsConfig,
+    threads: usize,
+) -> anyhow::Result<Stats> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let chunk_count = (len / CHUNK_MIN_BYTES).clamp(1, threads.max(1) as u64);
+    if chunk_count == 1 {
+        return compute(BufReader::new(file), config);
+    }
+    let bounds = chunk_bounds(&mut file, len, chunk_count)?;
+    let results: Vec<anyhow::Result<Stats>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = bounds
+            .windows(2)
+            .map(|w| {
+                let (start, end) = (w[0], w[1]);
+                scope.spawn(move || count_chunk(path, config, start, end))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| {
+                h.join()
+                    .unwrap_or_else(|_| Err(anyhow!("counting thread panicked")))
+            })
+            .collect()
+    });
+    let mut stats = Stats::default();
+    for r in results {
+        stats.merge(&r?);
+    }
+    Ok(stats)
+}
+
+fn chunk_bounds(file: &mut File, len: u64, chunk_count: u64) -> anyhow::Result<Vec<u64>> {
+    let mut bounds = vec![0];
+    let mut reader = BufReader::new(file);
+    let mut buf = Vec::new();
+    for k in 1..chunk_count {
+        let prev = *bounds.last().unwrap();
+        let pos = len * k / chunk_count;
+        if pos <= prev {
+            continue;
+        }
+        reader.seek(SeekFrom::Start(pos - 1))?;
+        buf.clear();
+        let n = reader.read_until(b'\n', &mut buf)?;
+        let bound = pos - 1 + n as u64;
+        if bound > prev && bound < len {
+            bounds.push(bound);
+        }
+    }
+    bounds.push(len);
+    Ok(bounds)
+}
+
+fn count_chunk(path: &str, config: &StatsConfig, start: u64, end: u64) -> anyhow::Result<Stats> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(start))?;
+    compute(BufReader::new(file.take(end - start)), config)
+}
+
+// the items are processed on the pool of the threads, and `each` receives
+// the results in the order of the items, as soon as they are ready.
+pub(crate) fn for_each_parallel<I, T, W, F>(
+    items: &[I],
+    threads: usize,
+    work: W,
+    mut each: F,
+) -> anyhow::Result<()>
+where
+    I: Sync,
+    T: Send,
+    W: Fn(&I) -> T + Sync,
+    F: FnMut(&I, T) -> anyhow::Result<()>,
+{
+    if threads <= 1 || items.len() <= 1 {
+        for item in items {
+            each(item, work(item))?;
+        }
+        return Ok(());
+    }
+    let next = AtomicUsize::new(0);
+    std::thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..threads.min(items.len()) {
+            let tx = tx.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                if idx >= items.len() {
+                    break;
+                }
+                // the receiver is gone when `each` failed.
+                if tx.send((idx, work(&items[idx]))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+        let mut pending = BTreeMap::new();
+        let mut idx = 0;
+        for (i, r) in rx {
+            pending.insert(i, r);
+            while let Some(r) = pending.remove(&idx) {
+                each(&items[idx], r)?;
+                idx += 1;
+            }
+        }
+        Ok(())
+    })
+}
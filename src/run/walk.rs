@@ -0,0 +1,124 @@
+use crate::conf::CmdOptConf;
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use runnel::RunnelIoe;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+// the head bytes to look for a NUL byte of the binary file.
+const BINARY_HEAD_BYTES: u64 = 8 * 1024;
+
+// the input files listed in the file of --files-from or --files0-from,
+// separated by `sep`. the empty names are skipped.
+pub(super) fn read_file_list(sioe: &RunnelIoe, path: &str, sep: u8) -> anyhow::Result<Vec<String>> {
+    let mut reader: Box<dyn BufRead + '_> = if path == "-" {
+        sioe.pg_in().lock_bufread()
+    } else {
+        Box::new(BufReader::new(std::fs::File::open(path)?))
+    };
+    let mut files = Vec::new();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        if reader.read_until(sep, &mut buf)? == 0 {
+            break;
+        }
+        if buf.last() == Some(&sep) {
+            buf.pop();
+        }
+        if sep == b'\n' && buf.last() == Some(&b'\r') {
+            buf.pop();
+        }
+        if !buf.is_empty() {
+            files.push(String::from_utf8_lossy(&buf).into_owned());
+        }
+    }
+    Ok(files)
+}
+
+// the input file, and the root directory of -r that it is found in.
+pub(super) struct InputFile {
+    pub(super) path: String,
+    pub(super) root: Option<String>,
+}
+
+// the files of the roots, with the files in the directories of -r.
+// the errors of the walking are returned with the files, in the order found.
+// the roots are walked by the path of -X base_dir=, and the files are
+// named under the roots as given.
+pub(super) fn collect_files(conf: &CmdOptConf, roots: &[String]) -> Vec<anyhow::Result<InputFile>> {
+    let mut files = Vec::new();
+    for root in roots {
+        if root == "-" {
+            files.push(Ok(InputFile {
+                path: root.to_string(),
+                root: None,
+            }));
+            continue;
+        }
+        let walk_root = conf.resolve_path(root);
+        if let Err(err) = std::fs::metadata(&walk_root) {
+            files.push(Err(anyhow!("{root}: {err}")));
+            continue;
+        }
+        let overrides = match build_overrides(conf, &walk_root) {
+            Ok(overrides) => overrides,
+            Err(err) => {
+                files.push(Err(err));
+                continue;
+            }
+        };
+        let walker = WalkBuilder::new(&walk_root)
+            .standard_filters(false)
+            .git_ignore(conf.flg_gitignore)
+            .git_exclude(conf.flg_gitignore)
+            .parents(conf.flg_gitignore)
+            .require_git(false)
+            .overrides(overrides)
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .build();
+        for entry in walker {
+            match entry {
+                Ok(entry) => {
+                    if entry.file_type().is_some_and(|ft| !ft.is_dir()) {
+                        let name = match entry.path().strip_prefix(&walk_root) {
+                            Ok(rel) if !rel.as_os_str().is_empty() => Path::new(root).join(rel),
+                            _ => PathBuf::from(root),
+                        };
+                        files.push(Ok(InputFile {
+                            path: name.to_string_lossy().into_owned(),
+                            root: Some(root.to_string()),
+                        }));
+                    }
+                }
+                Err(err) => files.push(Err(anyhow!("{err}"))),
+            }
+        }
+    }
+    files
+}
+
+fn build_overrides(conf: &CmdOptConf, root: &str) -> anyhow::Result<ignore::overrides::Override> {
+    let mut builder = OverrideBuilder::new(root);
+    for glob in &conf.opt_include {
+        builder.add(glob)?;
+    }
+    for glob in &conf.opt_exclude {
+        builder.add(&format!("!{glob}"))?;
+    }
+    Ok(builder.build()?)
+}
+
+// the file is binary if its head has a NUL byte, unless it is
+// the compressed file of --decompress.
+pub(super) fn is_binary(path: &str, decompress: bool) -> anyhow::Result<bool> {
+    let mut head = Vec::new();
+    std::fs::File::open(path)?
+        .take(BINARY_HEAD_BYTES)
+        .read_to_end(&mut head)?;
+    if decompress && super::decompress::is_compressed(&head) {
+        return Ok(false);
+    }
+    Ok(memx::memchr(&head, 0).is_some())
+}
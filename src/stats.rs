@@ -0,0 +1,306 @@
+use regex::Regex;
+use std::io::BufRead;
+
+/// The statistics of text.
+///
+/// The byte counts do not include the newlines.
+/// More statistics may be added in the later versions, so it is made by
+/// [`compute()`], [`Counter::stats()`] or `Stats::default()`, not by the struct expression.
+///
+/// [`Counter::stats()`]: crate::Counter::stats
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    pub byte_count: u64,
+    pub char_count: u64,
+    pub line_count: u64,
+    pub word_count: u64,
+    pub max_line_bytes: u64,
+    pub blank_count: u64,
+    pub ws_only_count: u64,
+    pub trailing_ws_count: u64,
+}
+
+impl Stats {
+    /// merge the statistics of the other text.
+    pub fn merge(&mut self, other: &Stats) {
+        self.byte_count += other.byte_count;
+        self.char_count += other.char_count;
+        self.line_count += other.line_count;
+        self.word_count += other.word_count;
+        self.max_line_bytes = self.max_line_bytes.max(other.max_line_bytes);
+        self.blank_count += other.blank_count;
+        self.ws_only_count += other.ws_only_count;
+        self.trailing_ws_count += other.trailing_ws_count;
+    }
+    pub(crate) fn count_up(&mut self, line_ss: &str, config: &StatsConfig) {
+        let line_bytes = line_ss.len() as u64;
+        //
+        self.line_count += 1;
+        self.byte_count += line_bytes;
+        self.max_line_bytes = self.max_line_bytes.max(line_bytes);
+        if config.count_words {
+            let mut prev_is_delim = true;
+            for c in line_ss.chars() {
+                self.char_count += 1;
+                let is_delim = config.word_delims.is_delim(c);
+                if prev_is_delim && !is_delim {
+                    self.word_count += 1;
+                }
+                prev_is_delim = is_delim;
+            }
+        }
+        if config.count_line_kinds {
+            if line_ss.is_empty() {
+                self.blank_count += 1;
+            } else {
+                if line_ss.chars().all(char::is_whitespace) {
+                    self.ws_only_count += 1;
+                }
+                if line_ss.ends_with([' ', '\t']) {
+                    self.trailing_ws_count += 1;
+                }
+            }
+        }
+    }
+}
+
+/// The configuration of [`compute()`].
+///
+/// The default configuration splits the text at the newlines,
+/// and delimits the words with the ascii white spaces.
+#[derive(Debug, Clone)]
+pub struct StatsConfig {
+    pub(crate) word_delims: WordDelims,
+    pub(crate) record_sep: Option<RecordSep>,
+    // the chars and the words
+    pub(crate) count_words: bool,
+    // the blank, the whitespace-only and the trailing-whitespace lines
+    pub(crate) count_line_kinds: bool,
+}
+
+impl Default for StatsConfig {
+    fn default() -> Self {
+        Self {
+            word_delims: WordDelims::new(""),
+            record_sep: None,
+            count_words: true,
+            count_line_kinds: true,
+        }
+    }
+}
+
+impl StatsConfig {
+    pub fn builder() -> StatsConfigBuilder {
+        StatsConfigBuilder::new()
+    }
+}
+
+/// The builder of [`StatsConfig`].
+#[derive(Debug, Default, Clone)]
+pub struct StatsConfigBuilder {
+    word_delims: String,
+    record_sep: Option<String>,
+    record_str: Option<String>,
+}
+
+impl StatsConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// extra characters that delimit words, in addition to the ascii white spaces.
+    pub fn word_delims(mut self, chars: &str) -> Self {
+        self.word_delims = chars.to_string();
+        self
+    }
+    /// record separator regex instead of newline.
+    ///
+    /// the whole input is read into memory to be split by the regex.
+    /// the regex meta characters are escaped to separate by them literally,
+    /// or [`record_str()`](Self::record_str) is used.
+    pub fn record_sep(mut self, regex: &str) -> Self {
+        self.record_sep = Some(regex.to_string());
+        self
+    }
+    /// record separator string instead of newline.
+    ///
+    /// the input is read record by record.
+    pub fn record_str(mut self, s: &str) -> Self {
+        self.record_str = Some(s.to_string());
+        self
+    }
+    /// return:
+    ///   - ok: the configuration
+    ///   - err: the record separator is not a valid regex, is an empty string,
+    ///     or both of the regex and the string are given
+    pub fn build(self) -> anyhow::Result<StatsConfig> {
+        let record_sep = match (self.record_sep, self.record_str) {
+            (Some(_), Some(_)) => {
+                return Err(anyhow!(
+                    "the record separator is given as both a regex and a string"
+                ))
+            }
+            (Some(ref s), None) => Some(RecordSep::Regex(Regex::new(s)?)),
+            (None, Some(s)) if s.is_empty() => {
+                return Err(anyhow!("the record separator string is empty"))
+            }
+            (None, Some(s)) => Some(RecordSep::Str(s.into_bytes())),
+            (None, None) => None,
+        };
+        Ok(StatsConfig {
+            word_delims: WordDelims::new(&self.word_delims),
+            record_sep,
+            ..Default::default()
+        })
+    }
+}
+
+/// compute the statistics of text.
+///
+/// params:
+///   - reader: the text
+///   - config: the configuration
+///
+/// return:
+///   - ok: the statistics
+///   - err: io error or invalid utf-8
+///
+/// # Examples
+///
+/// ```rust
+/// use libaki_stats::{compute, StatsConfig};
+///
+/// let text = "abc def\n\nghi\n";
+/// let stats = compute(text.as_bytes(), &StatsConfig::default()).unwrap();
+/// assert_eq!(stats.line_count, 3);
+/// assert_eq!(stats.word_count, 3);
+/// assert_eq!(stats.blank_count, 1);
+/// ```
+pub fn compute<R: BufRead>(reader: R, config: &StatsConfig) -> anyhow::Result<Stats> {
+    let mut stats = Stats::default();
+    for_each_record(reader, config, |record_ss, _end| {
+        stats.count_up(record_ss, config);
+        Ok(())
+    })?;
+    Ok(stats)
+}
+
+// the lines of the bytes in memory are found by `memx::memchr()`,
+// without copying them into the strings of `BufRead::lines()`.
+pub(crate) fn compute_bytes(bytes: &[u8], config: &StatsConfig) -> anyhow::Result<Stats> {
+    if config.record_sep.is_some() {
+        return compute(bytes, config);
+    }
+    let mut stats = Stats::default();
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        let line = match memx::memchr(rest, b'\n') {
+            Some(pos) => {
+                let line = &rest[..pos];
+                rest = &rest[pos + 1..];
+                line.strip_suffix(b"\r").unwrap_or(line)
+            }
+            None => std::mem::take(&mut rest),
+        };
+        stats.count_up(utf8_str(line)?, config);
+    }
+    Ok(stats)
+}
+
+// calls the function with the each line, or the each record of the record separator,
+// and with the newline or the separator that ends it, empty at the end of the text.
+pub(crate) fn for_each_record<R, F>(
+    mut reader: R,
+    config: &StatsConfig,
+    mut f: F,
+) -> anyhow::Result<()>
+where
+    R: BufRead,
+    F: FnMut(&str, &[u8]) -> anyhow::Result<()>,
+{
+    match config.record_sep {
+        Some(RecordSep::Regex(ref re)) => {
+            let mut text = String::new();
+            reader.read_to_string(&mut text)?;
+            let mut start = 0;
+            for mat in re.find_iter(&text) {
+                f(&text[start..mat.start()], mat.as_str().as_bytes())?;
+                start = mat.end();
+            }
+            if start < text.len() {
+                f(&text[start..], b"")?;
+            }
+        }
+        Some(RecordSep::Str(ref sep)) => {
+            // the record ends with the last byte of the separator.
+            let last = sep[sep.len() - 1];
+            let mut buf = Vec::new();
+            loop {
+                let n = reader.read_until(last, &mut buf)?;
+                if n == 0 {
+                    // the last record without the separator
+                    if !buf.is_empty() {
+                        f(utf8_str(&buf)?, b"")?;
+                    }
+                    break;
+                }
+                if buf.ends_with(sep) {
+                    buf.truncate(buf.len() - sep.len());
+                    f(utf8_str(&buf)?, sep)?;
+                    buf.clear();
+                }
+            }
+        }
+        None => {
+            // the same lines as `BufRead::lines()`, without "\n" or "\r\n".
+            let mut line_s = String::new();
+            while reader.read_line(&mut line_s)? > 0 {
+                let mut len = line_s.len();
+                let mut end: &[u8] = b"";
+                if line_s.ends_with('\n') {
+                    len -= 1;
+                    end = b"\n";
+                    if line_s[..len].ends_with('\r') {
+                        len -= 1;
+                        end = b"\r\n";
+                    }
+                }
+                f(&line_s[..len], end)?;
+                line_s.clear();
+            }
+        }
+    }
+    Ok(())
+}
+
+// the same error as `BufRead::lines()` on the invalid utf-8.
+fn utf8_str(bytes: &[u8]) -> std::io::Result<&str> {
+    std::str::from_utf8(bytes).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "stream did not contain valid UTF-8",
+        )
+    })
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum RecordSep {
+    Regex(Regex),
+    Str(Vec<u8>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct WordDelims {
+    chars: Vec<char>,
+}
+impl WordDelims {
+    pub(crate) fn new(s: &str) -> WordDelims {
+        Self {
+            chars: s.chars().collect(),
+        }
+    }
+    #[inline]
+    pub(crate) fn is_delim(&self, c: char) -> bool {
+        c.is_ascii_whitespace() || self.chars.contains(&c)
+    }
+}
@@ -6,3 +6,30 @@ pub use self::opt_uc_x_param::OptUcXParam;
 
 pub mod opt_locale_loc;
 pub use opt_locale_loc::OptLocaleLoc;
+
+mod opt_map_ascii_mode;
+pub use self::opt_map_ascii_mode::OptMapAsciiMode;
+
+mod opt_table_lang;
+pub use self::opt_table_lang::OptTableLang;
+
+mod opt_table_norm;
+pub use self::opt_table_norm::OptTableNorm;
+
+mod opt_table_type;
+pub use self::opt_table_type::OptTableType;
+
+mod opt_map_sort;
+pub use self::opt_map_sort::OptMapSort;
+
+mod opt_hash_alg;
+pub use self::opt_hash_alg::OptHashAlg;
+
+mod opt_code_lang;
+pub use self::opt_code_lang::OptCodeLang;
+
+mod opt_log_bucket;
+pub use self::opt_log_bucket::OptLogBucket;
+
+mod opt_group_by;
+pub use self::opt_group_by::OptGroupBy;
@@ -0,0 +1,71 @@
+//{{{ OptCodeLang
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OptCodeLang {
+    #[default]
+    Rust,
+    C,
+    Python,
+    Shell,
+    Toml,
+    // the language by the file extension
+    Auto,
+}
+
+impl ::std::str::FromStr for OptCodeLang {
+    type Err = OptCodeLangParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let oc = match s {
+            "rust" => OptCodeLang::Rust,
+            "c" => OptCodeLang::C,
+            "python" => OptCodeLang::Python,
+            "shell" => OptCodeLang::Shell,
+            "toml" => OptCodeLang::Toml,
+            "auto" => OptCodeLang::Auto,
+            _ => {
+                let s = format!("can not parse '{s}'");
+                return Err(OptCodeLangParseError::new(s));
+            }
+        };
+        Ok(oc)
+    }
+}
+
+impl ::std::fmt::Display for OptCodeLang {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            OptCodeLang::Rust => "rust",
+            OptCodeLang::C => "c",
+            OptCodeLang::Python => "python",
+            OptCodeLang::Shell => "shell",
+            OptCodeLang::Toml => "toml",
+            OptCodeLang::Auto => "auto",
+        };
+        write!(f, "{s}")
+    }
+}
+//}}} OptCodeLang
+
+//{{{ OptCodeLangParseError
+#[derive(Debug)]
+pub struct OptCodeLangParseError {
+    desc: String,
+}
+
+impl OptCodeLangParseError {
+    fn new(s: String) -> OptCodeLangParseError {
+        OptCodeLangParseError { desc: s }
+    }
+}
+
+impl ::std::fmt::Display for OptCodeLangParseError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        self.desc.fmt(f)
+    }
+}
+
+impl ::std::error::Error for OptCodeLangParseError {
+    fn description(&self) -> &str {
+        self.desc.as_str()
+    }
+}
+//}}} OptCodeLangParseError
@@ -0,0 +1,93 @@
+//{{{ OptGroupBy
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OptGroupBy {
+    #[default]
+    Ext,
+    Dir,
+    Depth(usize),
+}
+
+impl ::std::str::FromStr for OptGroupBy {
+    type Err = OptGroupByParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let oc = match s {
+            "ext" => OptGroupBy::Ext,
+            "dir" => OptGroupBy::Dir,
+            _ => {
+                let bs = "depth=";
+                match s.strip_prefix(bs).map(|n| n.parse::<usize>()) {
+                    Some(Ok(n)) if n > 0 => OptGroupBy::Depth(n),
+                    _ => {
+                        let s = format!("can not parse '{s}'");
+                        return Err(OptGroupByParseError::new(s));
+                    }
+                }
+            }
+        };
+        Ok(oc)
+    }
+}
+
+impl ::std::fmt::Display for OptGroupBy {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match *self {
+            OptGroupBy::Ext => write!(f, "ext"),
+            OptGroupBy::Dir => write!(f, "dir"),
+            OptGroupBy::Depth(n) => write!(f, "depth={n}"),
+        }
+    }
+}
+//}}} OptGroupBy
+
+//{{{ OptGroupByParseError
+#[derive(Debug)]
+pub struct OptGroupByParseError {
+    desc: String,
+}
+
+impl OptGroupByParseError {
+    fn new(s: String) -> OptGroupByParseError {
+        OptGroupByParseError { desc: s }
+    }
+}
+
+impl ::std::fmt::Display for OptGroupByParseError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        self.desc.fmt(f)
+    }
+}
+
+impl ::std::error::Error for OptGroupByParseError {
+    fn description(&self) -> &str {
+        self.desc.as_str()
+    }
+}
+//}}} OptGroupByParseError
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(OptGroupBy::from_str("ext").unwrap(), OptGroupBy::Ext);
+        assert_eq!(OptGroupBy::from_str("dir").unwrap(), OptGroupBy::Dir);
+        assert_eq!(
+            OptGroupBy::from_str("depth=2").unwrap(),
+            OptGroupBy::Depth(2)
+        );
+    }
+    #[test]
+    fn test_from_str_invalid() {
+        for s in ["other", "depth=", "depth=0", "depth=x"] {
+            let e = OptGroupBy::from_str(s).unwrap_err();
+            assert_eq!(e.to_string(), format!("can not parse '{s}'"));
+        }
+    }
+    #[test]
+    fn test_display() {
+        assert_eq!(OptGroupBy::Depth(3).to_string(), "depth=3");
+    }
+}
@@ -0,0 +1,64 @@
+//{{{ OptHashAlg
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OptHashAlg {
+    #[default]
+    Sha256,
+    Blake3,
+    Crc32,
+    Xxh3,
+}
+
+impl ::std::str::FromStr for OptHashAlg {
+    type Err = OptHashAlgParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let oc = match s {
+            "sha256" => OptHashAlg::Sha256,
+            "blake3" => OptHashAlg::Blake3,
+            "crc32" => OptHashAlg::Crc32,
+            "xxh3" => OptHashAlg::Xxh3,
+            _ => {
+                let s = format!("can not parse '{s}'");
+                return Err(OptHashAlgParseError::new(s));
+            }
+        };
+        Ok(oc)
+    }
+}
+
+impl ::std::fmt::Display for OptHashAlg {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            OptHashAlg::Sha256 => "sha256",
+            OptHashAlg::Blake3 => "blake3",
+            OptHashAlg::Crc32 => "crc32",
+            OptHashAlg::Xxh3 => "xxh3",
+        };
+        write!(f, "{s}")
+    }
+}
+//}}} OptHashAlg
+
+//{{{ OptHashAlgParseError
+#[derive(Debug)]
+pub struct OptHashAlgParseError {
+    desc: String,
+}
+
+impl OptHashAlgParseError {
+    fn new(s: String) -> OptHashAlgParseError {
+        OptHashAlgParseError { desc: s }
+    }
+}
+
+impl ::std::fmt::Display for OptHashAlgParseError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        self.desc.fmt(f)
+    }
+}
+
+impl ::std::error::Error for OptHashAlgParseError {
+    fn description(&self) -> &str {
+        self.desc.as_str()
+    }
+}
+//}}} OptHashAlgParseError
@@ -10,11 +10,35 @@ impl OptLocaleLoc {
     where
         T: ToString + ToFormattedString,
     {
-        if self.inner.is_none() {
-            v.to_string()
+        if let Some(loc) = self.inner {
+            v.to_formatted_string(&loc)
         } else {
-            v.to_formatted_string(&self.inner.unwrap())
+            v.to_string()
+        }
+    }
+    // the float is formatted up to 6 decimal places, without trailing zeros.
+    pub fn formatted_f64(&self, v: f64) -> String {
+        if !v.is_finite() || v.abs() >= u64::MAX as f64 {
+            return v.to_string();
+        }
+        let s = format!("{:.6}", v.abs());
+        let (int_s, frac_s) = s.split_once('.').unwrap_or((s.as_str(), ""));
+        let frac_s = frac_s.trim_end_matches('0');
+        let int_v: u64 = int_s.parse().unwrap_or(0);
+        let (minus, decimal) = match self.inner {
+            Some(loc) => (loc.minus_sign(), loc.decimal()),
+            None => ("-", "."),
+        };
+        let mut r = String::new();
+        if v < 0.0 && (int_v != 0 || !frac_s.is_empty()) {
+            r.push_str(minus);
+        }
+        r.push_str(&self.formatted_string(int_v));
+        if !frac_s.is_empty() {
+            r.push_str(decimal);
+            r.push_str(frac_s);
         }
+        r
     }
 }
 
@@ -39,8 +63,8 @@ impl ::std::str::FromStr for OptLocaleLoc {
 
 impl ::std::fmt::Display for OptLocaleLoc {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-        if self.inner.is_some() {
-            write!(f, "{}", self.inner.unwrap().name())
+        if let Some(loc) = self.inner {
+            write!(f, "{}", loc.name())
         } else {
             write!(f, "C")
         }
@@ -96,6 +120,19 @@ mod tests {
         assert_eq!(format!("{loc}"), "fr");
     }
     #[test]
+    fn test_formatted_f64_c() {
+        let loc = OptLocaleLoc::from_str("C").unwrap();
+        assert_eq!(loc.formatted_f64(1234567.5), "1234567.5");
+        assert_eq!(loc.formatted_f64(-0.25), "-0.25");
+        assert_eq!(loc.formatted_f64(3.0), "3");
+    }
+    #[test]
+    fn test_formatted_f64_en() {
+        let loc = OptLocaleLoc::from_str("en").unwrap();
+        assert_eq!(loc.formatted_f64(1234567.125), "1,234,567.125");
+        assert_eq!(loc.formatted_f64(-1234.0), "-1,234");
+    }
+    #[test]
     fn test_from_str_invalid() {
         let _col: OptLocaleLoc = match FromStr::from_str("other") {
             Ok(_c) => _c,
@@ -0,0 +1,58 @@
+//{{{ OptLogBucket
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OptLogBucket {
+    #[default]
+    Hour,
+    Minute,
+}
+
+impl ::std::str::FromStr for OptLogBucket {
+    type Err = OptLogBucketParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let oc = match s {
+            "hour" => OptLogBucket::Hour,
+            "minute" => OptLogBucket::Minute,
+            _ => {
+                let s = format!("can not parse '{s}'");
+                return Err(OptLogBucketParseError::new(s));
+            }
+        };
+        Ok(oc)
+    }
+}
+
+impl ::std::fmt::Display for OptLogBucket {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            OptLogBucket::Hour => "hour",
+            OptLogBucket::Minute => "minute",
+        };
+        write!(f, "{s}")
+    }
+}
+//}}} OptLogBucket
+
+//{{{ OptLogBucketParseError
+#[derive(Debug)]
+pub struct OptLogBucketParseError {
+    desc: String,
+}
+
+impl OptLogBucketParseError {
+    fn new(s: String) -> OptLogBucketParseError {
+        OptLogBucketParseError { desc: s }
+    }
+}
+
+impl ::std::fmt::Display for OptLogBucketParseError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        self.desc.fmt(f)
+    }
+}
+
+impl ::std::error::Error for OptLogBucketParseError {
+    fn description(&self) -> &str {
+        self.desc.as_str()
+    }
+}
+//}}} OptLogBucketParseError
@@ -0,0 +1,97 @@
+//{{{ OptMapAsciiMode
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OptMapAsciiMode {
+    #[default]
+    Void,
+    Plain,
+    Detailed,
+    Nonzero,
+}
+
+impl ::std::str::FromStr for OptMapAsciiMode {
+    type Err = OptMapAsciiModeParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let oc = match s {
+            "" | "plain" => OptMapAsciiMode::Plain,
+            "detailed" => OptMapAsciiMode::Detailed,
+            "nonzero" => OptMapAsciiMode::Nonzero,
+            _ => {
+                let s = format!("can not parse '{s}'");
+                return Err(OptMapAsciiModeParseError::new(s));
+            }
+        };
+        Ok(oc)
+    }
+}
+
+impl ::std::fmt::Display for OptMapAsciiMode {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            OptMapAsciiMode::Void => "void",
+            OptMapAsciiMode::Plain => "plain",
+            OptMapAsciiMode::Detailed => "detailed",
+            OptMapAsciiMode::Nonzero => "nonzero",
+        };
+        write!(f, "{s}")
+    }
+}
+//}}} OptMapAsciiMode
+
+//{{{ OptMapAsciiModeParseError
+#[derive(Debug)]
+pub struct OptMapAsciiModeParseError {
+    desc: String,
+}
+
+impl OptMapAsciiModeParseError {
+    fn new(s: String) -> OptMapAsciiModeParseError {
+        OptMapAsciiModeParseError { desc: s }
+    }
+}
+
+impl ::std::fmt::Display for OptMapAsciiModeParseError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        self.desc.fmt(f)
+    }
+}
+
+impl ::std::error::Error for OptMapAsciiModeParseError {
+    fn description(&self) -> &str {
+        self.desc.as_str()
+    }
+}
+//}}} OptMapAsciiModeParseError
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_display_detailed() {
+        let mode = OptMapAsciiMode::Detailed;
+        assert_eq!(format!("{mode}"), "detailed");
+    }
+    #[test]
+    fn test_from_str_empty() {
+        let mode = OptMapAsciiMode::from_str("").unwrap();
+        assert_eq!(mode, OptMapAsciiMode::Plain);
+    }
+    #[test]
+    fn test_from_str_nonzero() {
+        let mode = OptMapAsciiMode::from_str("nonzero").unwrap();
+        assert_eq!(mode, OptMapAsciiMode::Nonzero);
+    }
+    #[test]
+    fn test_from_str_invalid() {
+        let _mode: OptMapAsciiMode = match FromStr::from_str("other") {
+            Ok(_c) => _c,
+            Err(e) => {
+                assert_eq!(e.to_string(), "can not parse \'other\'");
+                return;
+            }
+        };
+        unreachable!();
+    }
+}
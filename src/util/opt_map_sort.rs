@@ -0,0 +1,58 @@
+//{{{ OptMapSort
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OptMapSort {
+    #[default]
+    Code,
+    Count,
+}
+
+impl ::std::str::FromStr for OptMapSort {
+    type Err = OptMapSortParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let oc = match s {
+            "code" => OptMapSort::Code,
+            "count" => OptMapSort::Count,
+            _ => {
+                let s = format!("can not parse '{s}'");
+                return Err(OptMapSortParseError::new(s));
+            }
+        };
+        Ok(oc)
+    }
+}
+
+impl ::std::fmt::Display for OptMapSort {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            OptMapSort::Code => "code",
+            OptMapSort::Count => "count",
+        };
+        write!(f, "{s}")
+    }
+}
+//}}} OptMapSort
+
+//{{{ OptMapSortParseError
+#[derive(Debug)]
+pub struct OptMapSortParseError {
+    desc: String,
+}
+
+impl OptMapSortParseError {
+    fn new(s: String) -> OptMapSortParseError {
+        OptMapSortParseError { desc: s }
+    }
+}
+
+impl ::std::fmt::Display for OptMapSortParseError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        self.desc.fmt(f)
+    }
+}
+
+impl ::std::error::Error for OptMapSortParseError {
+    fn description(&self) -> &str {
+        self.desc.as_str()
+    }
+}
+//}}} OptMapSortParseError
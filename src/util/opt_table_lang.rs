@@ -0,0 +1,64 @@
+//{{{ OptTableLang
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OptTableLang {
+    #[default]
+    Rust,
+    C,
+    Python,
+    Json,
+}
+
+impl ::std::str::FromStr for OptTableLang {
+    type Err = OptTableLangParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let oc = match s {
+            "rust" => OptTableLang::Rust,
+            "c" => OptTableLang::C,
+            "python" => OptTableLang::Python,
+            "json" => OptTableLang::Json,
+            _ => {
+                let s = format!("can not parse '{s}'");
+                return Err(OptTableLangParseError::new(s));
+            }
+        };
+        Ok(oc)
+    }
+}
+
+impl ::std::fmt::Display for OptTableLang {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            OptTableLang::Rust => "rust",
+            OptTableLang::C => "c",
+            OptTableLang::Python => "python",
+            OptTableLang::Json => "json",
+        };
+        write!(f, "{s}")
+    }
+}
+//}}} OptTableLang
+
+//{{{ OptTableLangParseError
+#[derive(Debug)]
+pub struct OptTableLangParseError {
+    desc: String,
+}
+
+impl OptTableLangParseError {
+    fn new(s: String) -> OptTableLangParseError {
+        OptTableLangParseError { desc: s }
+    }
+}
+
+impl ::std::fmt::Display for OptTableLangParseError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        self.desc.fmt(f)
+    }
+}
+
+impl ::std::error::Error for OptTableLangParseError {
+    fn description(&self) -> &str {
+        self.desc.as_str()
+    }
+}
+//}}} OptTableLangParseError
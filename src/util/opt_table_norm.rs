@@ -0,0 +1,61 @@
+//{{{ OptTableNorm
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OptTableNorm {
+    #[default]
+    Max,
+    Sum,
+    Log,
+}
+
+impl ::std::str::FromStr for OptTableNorm {
+    type Err = OptTableNormParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let oc = match s {
+            "max" => OptTableNorm::Max,
+            "sum" => OptTableNorm::Sum,
+            "log" => OptTableNorm::Log,
+            _ => {
+                let s = format!("can not parse '{s}'");
+                return Err(OptTableNormParseError::new(s));
+            }
+        };
+        Ok(oc)
+    }
+}
+
+impl ::std::fmt::Display for OptTableNorm {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            OptTableNorm::Max => "max",
+            OptTableNorm::Sum => "sum",
+            OptTableNorm::Log => "log",
+        };
+        write!(f, "{s}")
+    }
+}
+//}}} OptTableNorm
+
+//{{{ OptTableNormParseError
+#[derive(Debug)]
+pub struct OptTableNormParseError {
+    desc: String,
+}
+
+impl OptTableNormParseError {
+    fn new(s: String) -> OptTableNormParseError {
+        OptTableNormParseError { desc: s }
+    }
+}
+
+impl ::std::fmt::Display for OptTableNormParseError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        self.desc.fmt(f)
+    }
+}
+
+impl ::std::error::Error for OptTableNormParseError {
+    fn description(&self) -> &str {
+        self.desc.as_str()
+    }
+}
+//}}} OptTableNormParseError
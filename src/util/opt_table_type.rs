@@ -0,0 +1,61 @@
+//{{{ OptTableType
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OptTableType {
+    #[default]
+    U8,
+    U16,
+    F32,
+}
+
+impl ::std::str::FromStr for OptTableType {
+    type Err = OptTableTypeParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let oc = match s {
+            "u8" => OptTableType::U8,
+            "u16" => OptTableType::U16,
+            "f32" => OptTableType::F32,
+            _ => {
+                let s = format!("can not parse '{s}'");
+                return Err(OptTableTypeParseError::new(s));
+            }
+        };
+        Ok(oc)
+    }
+}
+
+impl ::std::fmt::Display for OptTableType {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let s = match *self {
+            OptTableType::U8 => "u8",
+            OptTableType::U16 => "u16",
+            OptTableType::F32 => "f32",
+        };
+        write!(f, "{s}")
+    }
+}
+//}}} OptTableType
+
+//{{{ OptTableTypeParseError
+#[derive(Debug)]
+pub struct OptTableTypeParseError {
+    desc: String,
+}
+
+impl OptTableTypeParseError {
+    fn new(s: String) -> OptTableTypeParseError {
+        OptTableTypeParseError { desc: s }
+    }
+}
+
+impl ::std::fmt::Display for OptTableTypeParseError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        self.desc.fmt(f)
+    }
+}
+
+impl ::std::error::Error for OptTableTypeParseError {
+    fn description(&self) -> &str {
+        self.desc.as_str()
+    }
+}
+//}}} OptTableTypeParseError
@@ -47,7 +47,7 @@ mod test_0_e {
                 program_name!(),
                 ": ",
                 "Invalid option: z\n",
-                "Missing option: b, c, l, w, a or --map-ascii\n",
+                "Missing option: b, c, l, w, a or another statistics option\n",
                 try_help_msg!()
             )
         );
@@ -62,7 +62,7 @@ mod test_0_e {
             concat!(
                 program_name!(),
                 ": ",
-                "Missing option: b, c, l, w, a or --map-ascii\n",
+                "Missing option: b, c, l, w, a or another statistics option\n",
                 "Unexpected argument: \n",
                 try_help_msg!()
             )
@@ -116,7 +116,7 @@ mod test_1_e {
             concat!(
                 program_name!(),
                 ": ",
-                "Missing option: b, c, l, w, a or --map-ascii\n",
+                "Missing option: b, c, l, w, a or another statistics option\n",
                 "Unexpected argument: \n",
                 try_help_msg!()
             )
@@ -491,3 +491,1258 @@ mod test_4_with_locale_e {
         assert!(oup.status.success());
     }
 }
+
+mod test_5_record_sep_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_semicolon() {
+        let input = "select 1; select 2;\nselect 3;\n";
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "-w", "--record-sep", ";"],
+            input.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "lines:\"4\", words:\"6\"\n");
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_semicolon_newline() {
+        let input = "select 1; select 2;\nselect 3;\n";
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "-b", "--record-sep", ";\\s*"],
+            input.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "lines:\"3\", bytes:\"24\"\n");
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_paragraphs() {
+        let input = "para 1 line 1\npara 1 line 2\n\npara 2\n\n\npara 3\n";
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "-w", "-m", "--record-sep", "\\n\\n+"],
+            input.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "lines:\"3\", words:\"12\", max:\"27\"\n");
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_empty_input() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["-l", "--record-sep", ";"], b"");
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "lines:\"0\"\n");
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_invalid_regex() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["-l", "--record-sep", "("], b"abc\n");
+        assert!(oup
+            .stderr
+            .starts_with(concat!(program_name!(), ": regex parse error")));
+        assert_eq!(oup.stdout, "");
+        assert!(!oup.status.success());
+    }
+    //
+    #[test]
+    fn test_record_str_literal() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "-w", "--record-str", "."],
+            b"a b.c.d e\n",
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "lines:\"3\", words:\"5\"\n");
+        assert!(oup.status.success());
+    }
+}
+
+mod test_5_word_delims_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_punctuation() {
+        let input = "hello, world! one-two three.four\n";
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-w", "--word-delims", ",.-"],
+            input.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "words:\"6\"\n");
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_unicode_delims() {
+        let input = "こんにちは、世界。\n";
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-w", "-c", "--word-delims", "、。"],
+            input.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "chars:\"9\", words:\"2\"\n");
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_with_record_sep() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "-w", "--record-sep", ";", "--word-delims", ","],
+            b"a,b;c,d;e",
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "lines:\"3\", words:\"5\"\n");
+        assert!(oup.status.success());
+    }
+}
+
+mod test_5_line_kinds_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_all_kinds() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "--blank", "--ws-only", "--trailing-ws"],
+            b"a\n\n  \nb  \n\t\nc\t\n",
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            "lines:\"6\", blank:\"1\", ws-only:\"2\", trailing-ws:\"4\"\n"
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_blank_only() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--blank"], b"a\n\n\nb\n");
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "blank:\"2\"\n");
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_crlf_trailing_ws() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--trailing-ws"], b"a \r\nb\r\n");
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "trailing-ws:\"1\"\n");
+        assert!(oup.status.success());
+    }
+}
+
+const IN_DAT_INDENT: &str = "\
+fn main() {
+    let x = 1;
+    if x > 0 {
+        run();
+    }
+\tlet y = 2;
+\t    let z = 3;
+
+}
+";
+
+mod test_5_indent_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_indent() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["--indent"],
+            super::IN_DAT_INDENT.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "indent: tab: 1\n",
+                "indent: space: 4\n",
+                "indent: mixed: 1\n",
+                "indent: unit: 4\n",
+                "indent: width: 4: 3\n",
+                "indent: width: 8: 1\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_indent_with_lines() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "--indent"],
+            b"a\n  b\n    c\n   d\n",
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"4\"\n",
+                "indent: tab: 0\n",
+                "indent: space: 3\n",
+                "indent: mixed: 0\n",
+                "indent: unit: -\n",
+                "indent: width: 2: 1\n",
+                "indent: width: 3: 1\n",
+                "indent: width: 4: 1\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_indent_empty() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["-l", "--indent"], b"");
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"0\"\n",
+                "indent: tab: 0\n",
+                "indent: space: 0\n",
+                "indent: mixed: 0\n",
+                "indent: unit: -\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+}
+
+const IN_DAT_INVISIBLES: &str = "\u{FEFF}abc\nde\u{200B}f\u{200B}\n\u{202E}x\u{0}y\ta\u{A0}b\n";
+
+mod test_5_invisibles_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_invisibles() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["--invisibles"],
+            super::IN_DAT_INVISIBLES.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "invisible: total: 5\n",
+                "invisible: U+0000 NUL: 1: at 3:3\n",
+                "invisible: U+00A0 NBSP: 1: at 3:7\n",
+                "invisible: U+200B ZWSP: 2: at 2:3, 2:5\n",
+                "invisible: U+202E RLO: 1: at 3:1\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_invisibles_locations() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "--invisibles", "--locations", "1"],
+            super::IN_DAT_INVISIBLES.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"3\"\n",
+                "invisible: total: 5\n",
+                "invisible: U+0000 NUL: 1: at 3:3\n",
+                "invisible: U+00A0 NBSP: 1: at 3:7\n",
+                "invisible: U+200B ZWSP: 2: at 2:3\n",
+                "invisible: U+202E RLO: 1: at 3:1\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_invisibles_none() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["--invisibles", "--locations", "0"],
+            b"abc\n\tdef\n",
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "invisible: total: 0\n");
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_invalid_locations() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--invisibles", "--locations", "x"], b"");
+        assert_eq!(
+            oup.stderr,
+            concat!(
+                program_name!(),
+                ": Invalid option argument: locations: invalid digit found in string\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(oup.stdout, "");
+        assert!(!oup.status.success());
+    }
+}
+
+mod test_5_map_ascii_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_plain() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--map-ascii"], b"ab\x1fa\tb\n");
+        assert_eq!(oup.stderr, "");
+        assert!(oup
+            .stdout
+            .starts_with("ctrl: --: 1\nctrl: ht: 1\nctrl: vt: 0\n0x20: SP: 0\n"));
+        assert!(oup.stdout.contains("\n0x61:  a: 2\n"));
+        assert!(oup.stdout.ends_with("\n0x7e:  ~: 0\n"));
+        assert_eq!(oup.stdout.lines().count(), 98);
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_no_empty_stats_line() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--map-ascii"], b"ab\n");
+        assert_eq!(oup.stderr, "");
+        assert!(oup.stdout.starts_with("ctrl: --: 0\n"));
+        assert!(oup.status.success());
+        let oup2 = exec_target_with_in(TARGET_EXE_PATH, ["-l", "--map-ascii"], b"ab\n");
+        assert_eq!(oup2.stderr, "");
+        assert_eq!(oup2.stdout, format!("lines:\"1\"\n{}", oup.stdout));
+        assert!(oup2.status.success());
+    }
+    //
+    #[test]
+    fn test_detailed() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--map-ascii=detailed"], b"ab\x1fa\tb\n");
+        assert_eq!(oup.stderr, "");
+        assert!(oup
+            .stdout
+            .starts_with("0x00: NUL: 0: 0.00%\n0x01: SOH: 0: 0.00%\n"));
+        assert!(oup
+            .stdout
+            .contains("\n0x1f:  US: 1: 14.29%\n0x20:  SP: 0: 0.00%\n"));
+        assert!(oup.stdout.contains("\n0x0a:  LF: 1: 14.29%\n"));
+        assert!(oup.stdout.ends_with("\n0x7f: DEL: 0: 0.00%\n"));
+        assert_eq!(oup.stdout.lines().count(), 128);
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_nonzero() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "--map-ascii=nonzero"],
+            b"ab\x1fa\tb\n",
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"1\"\n",
+                "0x09:  HT: 1: 14.29%\n",
+                "0x0a:  LF: 1: 14.29%\n",
+                "0x1f:  US: 1: 14.29%\n",
+                "0x61:   a: 2: 28.57%\n",
+                "0x62:   b: 2: 28.57%\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_nonzero_crlf() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--map-ascii=nonzero"], b"a\tb\r\nc\x1f\n");
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "0x09:  HT: 1: 12.50%\n",
+                "0x0a:  LF: 2: 25.00%\n",
+                "0x0d:  CR: 1: 12.50%\n",
+                "0x1f:  US: 1: 12.50%\n",
+                "0x61:   a: 1: 12.50%\n",
+                "0x62:   b: 1: 12.50%\n",
+                "0x63:   c: 1: 12.50%\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_invalid_mode() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--map-ascii=other"], b"");
+        assert_eq!(
+            oup.stderr,
+            concat!(
+                program_name!(),
+                ": Invalid option argument: map-ascii: can not parse 'other'\n",
+                "Missing option: b, c, l, w, a or another statistics option\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(oup.stdout, "");
+        assert!(!oup.status.success());
+    }
+}
+
+fn table_vals(zero: &str, vals: &[(usize, &str)]) -> String {
+    let mut vec: Vec<&str> = vec![zero; 128];
+    for (i, v) in vals {
+        vec[*i] = v;
+    }
+    vec.join(", ")
+}
+
+mod test_5_emit_table_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_rust() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--emit-table", "rust"], b"aab\n");
+        assert_eq!(oup.stderr, "");
+        let vals = super::table_vals("0", &[(0x61, "255"), (0x62, "127")]);
+        assert_eq!(
+            oup.stdout,
+            format!("const ASCII_STOCHAS: [u8;128] = [{vals}];\n")
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_x_map_ascii_rust_src_empty() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["--map-ascii", "-X", "map-ascii-rust-src"],
+            b"",
+        );
+        assert_eq!(oup.stderr, "");
+        let vals = super::table_vals("0", &[]);
+        assert_eq!(
+            oup.stdout,
+            format!("const ASCII_STOCHAS: [u8;128] = [{vals}];\n")
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_c_u16_sum() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            [
+                "--emit-table",
+                "c",
+                "--table-type",
+                "u16",
+                "--table-norm",
+                "sum",
+                "--table-name",
+                "freq",
+            ],
+            b"aab\n",
+        );
+        assert_eq!(oup.stderr, "");
+        let vals = super::table_vals("0", &[(0x61, "43690"), (0x62, "21845")]);
+        assert_eq!(
+            oup.stdout,
+            format!("const uint16_t freq[128] = {{{vals}}};\n")
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_json_f32_log() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            [
+                "--emit-table",
+                "json",
+                "--table-type",
+                "f32",
+                "--table-norm",
+                "log",
+            ],
+            b"aab\n",
+        );
+        assert_eq!(oup.stderr, "");
+        let vals = super::table_vals("null", &[(0x61, "-0.4054651"), (0x62, "-1.0986123")]);
+        assert_eq!(oup.stdout, format!("{{\"ASCII_STOCHAS\": [{vals}]}}\n"));
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_log_needs_f32() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["--emit-table", "c", "--table-norm", "log"],
+            b"",
+        );
+        assert_eq!(
+            oup.stderr,
+            concat!(
+                program_name!(),
+                ": Invalid option argument: table-norm: 'log' needs the table type 'f32'\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(oup.stdout, "");
+        assert!(!oup.status.success());
+    }
+}
+
+mod test_5_map_bar_e {
+    use exec_target::{exec_target_with_env_in, exec_target_with_in};
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_sort_count() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["--map-ascii=nonzero", "--map-sort", "count"],
+            b"abb\tc\n",
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "0x62:   b: 2: 33.33%\n",
+                "0x09:  HT: 1: 16.67%\n",
+                "0x0a:  LF: 1: 16.67%\n",
+                "0x61:   a: 1: 16.67%\n",
+                "0x63:   c: 1: 16.67%\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_bar_columns() {
+        let env: [(&str, &str); 1] = [("COLUMNS", "36")];
+        let oup = exec_target_with_env_in(
+            TARGET_EXE_PATH,
+            ["--map-ascii=nonzero", "--map-bar"],
+            env,
+            b"abb\tc\n",
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "0x09:  HT: 1: 16.67%: ███████\n",
+                "0x0a:  LF: 1: 16.67%: ███████\n",
+                "0x61:   a: 1: 16.67%: ███████\n",
+                "0x62:   b: 2: 33.33%: ██████████████\n",
+                "0x63:   c: 1: 16.67%: ███████\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_bar_width_over_columns() {
+        let env: [(&str, &str); 1] = [("COLUMNS", "200")];
+        let oup = exec_target_with_env_in(
+            TARGET_EXE_PATH,
+            ["--map-ascii=nonzero", "--map-bar", "--map-width", "36"],
+            env,
+            b"abb\tc\n",
+        );
+        assert_eq!(oup.stderr, "");
+        assert!(oup
+            .stdout
+            .contains("0x62:   b: 2: 33.33%: ██████████████\n"));
+        assert!(oup.status.success());
+    }
+}
+
+const IN_DAT_PATTERN: &str = "foo bar foo\nbaz\nfoo.\n";
+
+mod test_5_pattern_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_patterns() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "-e", "foo", "--pattern", "ba[rz]"],
+            super::IN_DAT_PATTERN.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"3\", ",
+                "matches:foo:\"3\", matched-lines:foo:\"2\", ",
+                "matches:ba[rz]:\"2\", matched-lines:ba[rz]:\"2\"\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_fixed_strings() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-F", "-e", "o."],
+            super::IN_DAT_PATTERN.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "matches:o.:\"1\", matched-lines:o.:\"1\"\n");
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_invalid_regex() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-e", "("],
+            super::IN_DAT_PATTERN.as_bytes(),
+        );
+        assert!(oup
+            .stderr
+            .starts_with(concat!(program_name!(), ": regex parse error")));
+        assert_eq!(oup.stdout, "");
+        assert!(!oup.status.success());
+    }
+}
+
+const IN_DAT_DUP_LINES: &str = "b\na\nb\n A\nc\nb\na\n";
+
+mod test_5_dup_lines_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_dup_lines() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["--dup-lines"],
+            super::IN_DAT_DUP_LINES.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "dup-lines: distinct: 4\n",
+                "dup-lines: duplicated: 2\n",
+                "dup-lines: top: 3: b\n",
+                "dup-lines: top: 2: a\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_trim_fold_top() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["--dup-lines", "--dup-trim", "--dup-fold", "--dup-top", "1"],
+            super::IN_DAT_DUP_LINES.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "dup-lines: distinct: 3\n",
+                "dup-lines: duplicated: 2\n",
+                "dup-lines: top: 3: a\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_approx() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["--dup-lines", "--dup-approx", "100"],
+            super::IN_DAT_DUP_LINES.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "dup-lines: distinct: ~4\n",
+                "dup-lines: duplicated: ~2\n",
+                "dup-lines: top: ~3: b\n",
+                "dup-lines: top: ~2: a\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+}
+
+mod test_5_hash_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_sha256() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--hash", "sha256"], b"abc");
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            "sha256:\"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\"\n"
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_xxh3() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--hash", "xxh3"], b"abc");
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "xxh3:\"78af5f94892f3950\"\n");
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_exact_bytes() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "-b", "--hash", "crc32"],
+            b"abc\r\nde",
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "lines:\"2\", bytes:\"5\", crc32:\"e8b75d9c\"\n");
+        assert!(oup.status.success());
+    }
+}
+
+const IN_DAT_FIELDS: &str =
+    "id,name,note\n1,\"Smith, J\",\"a \"\"q\"\"\"\n2,,\"multi\nline\"\n3,x\n\n";
+
+mod test_5_fields_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_csv() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "--fields"],
+            super::IN_DAT_FIELDS.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"6\"\n",
+                "fields: rows: 4\n",
+                "fields: consistent: no\n",
+                "fields: field-count: 2: 1\n",
+                "fields: field-count: 3: 3\n",
+                "fields: column: 1: non-empty: 4, distinct: 4, width: 1..2\n",
+                "fields: column: 2: non-empty: 3, distinct: 4, width: 0..8\n",
+                "fields: column: 3: non-empty: 3, distinct: 3, width: 4..10\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_tab() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--fields", "-t"], b"a\tb\n\tc\n");
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "fields: rows: 2\n",
+                "fields: consistent: yes\n",
+                "fields: field-count: 2: 2\n",
+                "fields: column: 1: non-empty: 1, distinct: 2, width: 0..1\n",
+                "fields: column: 2: non-empty: 2, distinct: 2, width: 1..1\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+}
+
+const IN_DAT_NUMERIC: &str = "name,val\na,1000\nb,2500.5\nc,x\nd,-3\ne\n";
+
+mod test_5_numeric_field_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_with_locale() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["--numeric-field", "2", "--locale", "en"],
+            super::IN_DAT_NUMERIC.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "numeric: count: 3\n",
+                "numeric: skipped: 3\n",
+                "numeric: sum: 3,497.5\n",
+                "numeric: min: -3\n",
+                "numeric: max: 2,500.5\n",
+                "numeric: mean: 1,165.833333\n",
+                "numeric: stddev: 1,028.754452\n",
+                "numeric: p50: 1,000\n",
+                "numeric: p90: 2,200.4\n",
+                "numeric: p99: 2,470.49\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+}
+
+const IN_DAT_CODE_RUST: &str = "\
+// line comment
+fn f<'a>(s: &'a str) -> char {
+    /* block
+       /* nested */
+       still */
+    let q = '\"'; // quote char
+
+    let s = \"multi
+// not a comment
+\";
+    '\\n' /* trailing */
+}
+";
+
+mod test_5_code_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_rust() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "--code", "rust"],
+            super::IN_DAT_CODE_RUST.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            "lines:\"12\", code:\"7\", comments:\"4\", blanks:\"1\"\n"
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_python() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["--code", "python"],
+            b"#!/usr/bin/env python3\n\"\"\"doc\nstring\"\"\"\n\nx = '#not comment'  # comment\n",
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "code:\"3\", comments:\"1\", blanks:\"1\"\n");
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_auto_stdin() {
+        // the language of the stdin is not detected.
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--code", "auto"], b"fn main(){}\n");
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "code:\"-\", comments:\"-\", blanks:\"-\"\n");
+        assert!(oup.status.success());
+    }
+}
+
+const IN_DAT_MARKDOWN: &str = "\
+# Title
+
+Some *prose* with a [link](http://x) and ![img](a.png).
+
+Sub
+---
+
+- item one
+- item two
+1. first
+
+```rust
+let x = 1;
+// not words
+```
+
+> ## quoted heading
+";
+
+mod test_5_markdown_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_markdown() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["--markdown"],
+            super::IN_DAT_MARKDOWN.as_bytes(),
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "markdown: heading: h1: 1\n",
+                "markdown: heading: h2: 2\n",
+                "markdown: heading: h3: 0\n",
+                "markdown: heading: h4: 0\n",
+                "markdown: heading: h5: 0\n",
+                "markdown: heading: h6: 0\n",
+                "markdown: code-blocks: 1\n",
+                "markdown: code-lines: 2\n",
+                "markdown: links: 1\n",
+                "markdown: images: 1\n",
+                "markdown: list-items: 3\n",
+                "markdown: words: 16\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+}
+
+const IN_DAT_LOG: &str = "\
+2024-01-02T03:04:05Z INFO start
+2024-01-02 03:59:00 WARNING disk
+  at stack frame
+2024-01-02 04:00:01 ERROR failed
+127.0.0.1 - - [02/Jan/2024:04:10:00 +0000] \"GET / HTTP/1.1\" 200 12
+Jan  2 05:00:00 host sshd[1]: DEBUG accepted
+";
+
+mod test_5_log_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_log() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--log"], super::IN_DAT_LOG.as_bytes());
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "log: ERROR: 1\n",
+                "log: WARN: 1\n",
+                "log: INFO: 1\n",
+                "log: DEBUG: 1\n",
+                "log: other: 2\n",
+                "log: first: 2024-01-02 03:04:05\n",
+                "log: last: 01-02 05:00:00\n",
+                "log: per-hour: 01-02 05: 1\n",
+                "log: per-hour: 2024-01-02 03: 2\n",
+                "log: per-hour: 2024-01-02 04: 2\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+}
+
+mod test_5_files_e {
+    use exec_target::{exec_target, exec_target_with_in};
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_two_files() {
+        let oup = exec_target(
+            TARGET_EXE_PATH,
+            ["-l", "-b", fixture_sample_text!(), fixture_sherlock!()],
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"10\", bytes:\"120\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"26\", bytes:\"1207\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"36\", bytes:\"1327\", file:\"total\"\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_two_files_code() {
+        let oup = exec_target(
+            TARGET_EXE_PATH,
+            [
+                "-l",
+                "--code",
+                "python",
+                fixture_sample_text!(),
+                fixture_sherlock!(),
+            ],
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"10\", code:\"10\", comments:\"0\", blanks:\"0\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"26\", code:\"24\", comments:\"0\", blanks:\"2\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"36\", code:\"34\", comments:\"0\", blanks:\"2\", file:\"total\"\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_stdin_dash() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            [
+                "-l",
+                "--threads",
+                "2",
+                fixture_sherlock!(),
+                "-",
+                fixture_sample_text!(),
+            ],
+            b"a\nb\n",
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"26\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"2\", file:\"-\"\n",
+                "lines:\"10\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"38\", file:\"total\"\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_threads_files() {
+        // the files are output in the order of the arguments,
+        // and an error does not stop the others.
+        let oup = exec_target(
+            TARGET_EXE_PATH,
+            [
+                "-l",
+                "-w",
+                "--threads",
+                "3",
+                fixture_sample_text!(),
+                fixture_sherlock!(),
+                "fixtures/not-found.txt",
+                fixture_sherlock!(),
+                fixture_sample_text!(),
+            ],
+        );
+        assert!(oup
+            .stderr
+            .starts_with(concat!(program_name!(), ": fixtures/not-found.txt: ")));
+        assert!(oup.stderr.ends_with(concat!(
+            program_name!(),
+            ": 1 of 5 files could not be processed\n"
+        )));
+        assert_eq!(oup.stderr.lines().count(), 2);
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"10\", words:\"10\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"26\", words:\"226\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"26\", words:\"226\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"10\", words:\"10\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"72\", words:\"472\", file:\"total\"\n",
+            )
+        );
+        assert!(!oup.status.success());
+    }
+    //
+    #[test]
+    fn test_threads_cpus() {
+        let oup = exec_target(
+            TARGET_EXE_PATH,
+            [
+                "-l",
+                "--threads",
+                "0",
+                fixture_sample_text!(),
+                fixture_sherlock!(),
+            ],
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"10\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"26\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"36\", file:\"total\"\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+    //
+    #[test]
+    fn test_threads() {
+        let oup = exec_target(
+            TARGET_EXE_PATH,
+            ["-a", "--threads", "4", fixture_sherlock!()],
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"26\", bytes:\"1207\", chars:\"1207\", words:\"226\", max:\"83\", ",
+                "file:\"fixtures/sherlock.txt\"\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+}
+
+#[cfg(feature = "decompress")]
+mod test_5_decompress_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_gzip_stdin() {
+        let in_bytes = std::fs::read(fixture_sherlock_gz!()).unwrap();
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["-l", "-w", "--decompress"], &in_bytes);
+        assert_eq!(oup.stderr, "");
+        assert_eq!(oup.stdout, "lines:\"26\", words:\"226\"\n");
+        assert!(oup.status.success());
+    }
+}
+
+mod test_5_compressed_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_without_decompress() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["--compressed"], b"x\n");
+        assert_eq!(
+            oup.stderr,
+            concat!(
+                program_name!(),
+                ": ",
+                "Invalid option: --compressed: needs --decompress\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(oup.stdout, "");
+        assert!(!oup.status.success());
+    }
+}
+
+#[cfg(not(feature = "decompress"))]
+mod test_5_no_decompress_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_no_feature() {
+        let oup = exec_target_with_in(TARGET_EXE_PATH, ["-l", "--decompress"], b"abc\n");
+        assert_eq!(
+            oup.stderr,
+            concat!(
+                program_name!(),
+                ": ",
+                "Invalid option: --decompress: needs the `decompress` feature of the build\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(oup.stdout, "");
+        assert!(!oup.status.success());
+    }
+}
+
+mod test_5_recursive_e {
+    use exec_target::exec_target;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_include() {
+        let oup = exec_target(
+            TARGET_EXE_PATH,
+            ["-l", "-r", "--include", "s*.txt", "fixtures"],
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"10\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"26\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"36\", file:\"total\"\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+}
+
+mod test_5_files_from_e {
+    use exec_target::exec_target_with_in;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_files0_from_stdin() {
+        let oup = exec_target_with_in(
+            TARGET_EXE_PATH,
+            ["-l", "--files0-from", "-"],
+            b"fixtures/sample-text.txt\0fixtures/sherlock.txt\0",
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"10\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"26\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"36\", file:\"total\"\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+}
+
+mod test_5_group_by_e {
+    use exec_target::exec_target;
+    const TARGET_EXE_PATH: &str = super::TARGET_EXE_PATH;
+    //
+    #[test]
+    fn test_ext() {
+        let oup = exec_target(
+            TARGET_EXE_PATH,
+            [
+                "-l",
+                "-r",
+                "--include",
+                "s*",
+                "--group-by",
+                "ext",
+                "fixtures",
+            ],
+        );
+        assert_eq!(oup.stderr, "");
+        assert_eq!(
+            oup.stdout,
+            concat!(
+                "lines:\"10\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"26\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"36\", group:\".txt\"\n",
+                "lines:\"36\", file:\"total\"\n",
+            )
+        );
+        assert!(oup.status.success());
+    }
+}
@@ -46,7 +46,7 @@ mod test_0_l {
                 program_name!(),
                 ": ",
                 "Invalid option: z\n",
-                "Missing option: b, c, l, w, a or --map-ascii\n",
+                "Missing option: b, c, l, w, a or another statistics option\n",
                 try_help_msg!()
             )
         );
@@ -61,7 +61,7 @@ mod test_0_l {
             buff!(sioe, serr),
             concat!(
                 program_name!(), ": ",
-                "Missing option: b, c, l, w, a or --map-ascii\n",
+                "Missing option: b, c, l, w, a or another statistics option\n",
                 "Unexpected argument: \n",
                 try_help_msg!()
             )
@@ -116,7 +116,7 @@ mod test_1_l {
             buff!(sioe, serr),
             concat!(
                 program_name!(), ": ",
-                "Missing option: b, c, l, w, a or --map-ascii\n",
+                "Missing option: b, c, l, w, a or another statistics option\n",
                 "Unexpected argument: \n",
                 try_help_msg!()
             )
@@ -484,3 +484,2493 @@ mod test_4_with_locale_l {
         assert!(r.is_ok());
     }
 }
+
+mod test_5_record_sep_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_semicolon() {
+        let input = "select 1; select 2;\nselect 3;\n";
+        let (r, sioe) = do_execute!(["-l", "-w", "--record-sep", ";"], input);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "lines:\"4\", words:\"6\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_semicolon_newline() {
+        let input = "select 1; select 2;\nselect 3;\n";
+        let (r, sioe) = do_execute!(["-l", "-b", "--record-sep", ";\\s*"], input);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "lines:\"3\", bytes:\"24\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_paragraphs() {
+        let input = "para 1 line 1\npara 1 line 2\n\npara 2\n\n\npara 3\n";
+        let (r, sioe) = do_execute!(["-l", "-w", "-m", "--record-sep", "\\n\\n+"], input);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "lines:\"3\", words:\"12\", max:\"27\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_empty_input() {
+        let (r, sioe) = do_execute!(["-l", "--record-sep", ";"], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "lines:\"0\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_invalid_regex() {
+        let (r, sioe) = do_execute!(["-l", "--record-sep", "("], "abc\n");
+        assert!(buff!(sioe, serr).starts_with(concat!(program_name!(), ": regex parse error")));
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+    //
+    #[test]
+    fn test_record_str_literal() {
+        let (r, sioe) = do_execute!(["-l", "-w", "--record-str", "."], "a b.c.d e\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "lines:\"3\", words:\"5\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_record_str_multi_bytes() {
+        let (r, sioe) = do_execute!(["-l", "-b", "--record-str", "||"], "a||b|c||||d");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "lines:\"4\", bytes:\"5\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_record_str_with_record_sep() {
+        let (r, sioe) = do_execute!(["-l", "--record-str", ";", "--record-sep", ";"], "a;b");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": the record separator is given as both a regex and a string\n"
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+mod test_5_word_delims_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_punctuation() {
+        let input = "hello, world! one-two three.four\n";
+        let (r, sioe) = do_execute!(["-w", "--word-delims", ",.-"], input);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "words:\"6\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_unicode_delims() {
+        let input = "こんにちは、世界。\n";
+        let (r, sioe) = do_execute!(["-w", "-c", "--word-delims", "、。"], input);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "chars:\"9\", words:\"2\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_with_record_sep() {
+        let input = "a,b;c,d;e";
+        let (r, sioe) = do_execute!(
+            ["-l", "-w", "--record-sep", ";", "--word-delims", ","],
+            input
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "lines:\"3\", words:\"5\"\n");
+        assert!(r.is_ok());
+    }
+}
+
+mod test_5_line_kinds_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_all_kinds() {
+        let input = "a\n\n  \nb  \n\t\nc\t\n";
+        let (r, sioe) = do_execute!(["-l", "--blank", "--ws-only", "--trailing-ws"], input);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "lines:\"6\", blank:\"1\", ws-only:\"2\", trailing-ws:\"4\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_blank_only() {
+        let (r, sioe) = do_execute!(["--blank"], "a\n\n\nb\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "blank:\"2\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_crlf_trailing_ws() {
+        let (r, sioe) = do_execute!(["--trailing-ws"], "a \r\nb\r\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "trailing-ws:\"1\"\n");
+        assert!(r.is_ok());
+    }
+}
+
+const IN_DAT_INDENT: &str = "\
+fn main() {
+    let x = 1;
+    if x > 0 {
+        run();
+    }
+\tlet y = 2;
+\t    let z = 3;
+
+}
+";
+
+mod test_5_indent_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_indent() {
+        let (r, sioe) = do_execute!(["--indent"], super::IN_DAT_INDENT);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "indent: tab: 1\n",
+                "indent: space: 4\n",
+                "indent: mixed: 1\n",
+                "indent: unit: 4\n",
+                "indent: width: 4: 3\n",
+                "indent: width: 8: 1\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_indent_with_lines() {
+        let (r, sioe) = do_execute!(["-l", "--indent"], "a\n  b\n    c\n   d\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"4\"\n",
+                "indent: tab: 0\n",
+                "indent: space: 3\n",
+                "indent: mixed: 0\n",
+                "indent: unit: -\n",
+                "indent: width: 2: 1\n",
+                "indent: width: 3: 1\n",
+                "indent: width: 4: 1\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_indent_empty() {
+        let (r, sioe) = do_execute!(["-l", "--indent"], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"0\"\n",
+                "indent: tab: 0\n",
+                "indent: space: 0\n",
+                "indent: mixed: 0\n",
+                "indent: unit: -\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+}
+
+const IN_DAT_INVISIBLES: &str = "\u{FEFF}abc\nde\u{200B}f\u{200B}\n\u{202E}x\u{0}y\ta\u{A0}b\n";
+
+mod test_5_invisibles_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_invisibles() {
+        let (r, sioe) = do_execute!(["--invisibles"], super::IN_DAT_INVISIBLES);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "invisible: total: 5\n",
+                "invisible: U+0000 NUL: 1: at 3:3\n",
+                "invisible: U+00A0 NBSP: 1: at 3:7\n",
+                "invisible: U+200B ZWSP: 2: at 2:3, 2:5\n",
+                "invisible: U+202E RLO: 1: at 3:1\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_invisibles_locations() {
+        let (r, sioe) = do_execute!(
+            ["-l", "--invisibles", "--locations", "1"],
+            super::IN_DAT_INVISIBLES
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"3\"\n",
+                "invisible: total: 5\n",
+                "invisible: U+0000 NUL: 1: at 3:3\n",
+                "invisible: U+00A0 NBSP: 1: at 3:7\n",
+                "invisible: U+200B ZWSP: 2: at 2:3\n",
+                "invisible: U+202E RLO: 1: at 3:1\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_invisibles_none() {
+        let (r, sioe) = do_execute!(["--invisibles", "--locations", "0"], "abc\n\tdef\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "invisible: total: 0\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_invalid_locations() {
+        let (r, sioe) = do_execute!(["--invisibles", "--locations", "x"], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": Invalid option argument: locations: invalid digit found in string\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+mod test_5_map_ascii_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_plain() {
+        let (r, sioe) = do_execute!(["--map-ascii"], "ab\x1fa\tb\n");
+        assert_eq!(buff!(sioe, serr), "");
+        let sout = buff!(sioe, sout);
+        assert!(sout.starts_with("ctrl: --: 1\nctrl: ht: 1\nctrl: vt: 0\n0x20: SP: 0\n"));
+        assert!(sout.contains("\n0x61:  a: 2\n"));
+        assert!(sout.ends_with("\n0x7e:  ~: 0\n"));
+        assert_eq!(sout.lines().count(), 98);
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_no_empty_stats_line() {
+        // the statistics line is omitted, not output as an empty line.
+        let (r, sioe) = do_execute!(["--map-ascii"], "ab\n");
+        assert_eq!(buff!(sioe, serr), "");
+        let sout = buff!(sioe, sout);
+        assert!(r.is_ok());
+        let (r, sioe) = do_execute!(["-l", "--map-ascii"], "ab\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), format!("lines:\"1\"\n{sout}"));
+        assert!(!sout.starts_with('\n'));
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_plain_explicit() {
+        let (r, sioe) = do_execute!(["--map-ascii=plain"], "ab\x1fa\tb\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert!(buff!(sioe, sout).starts_with("ctrl: --: 1\nctrl: ht: 1\n"));
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_detailed() {
+        let (r, sioe) = do_execute!(["--map-ascii=detailed"], "ab\x1fa\tb\n");
+        assert_eq!(buff!(sioe, serr), "");
+        let sout = buff!(sioe, sout);
+        assert!(sout.starts_with("0x00: NUL: 0: 0.00%\n0x01: SOH: 0: 0.00%\n"));
+        assert!(sout.contains("\n0x09:  HT: 1: 14.29%\n0x0a:  LF: 1: 14.29%\n"));
+        assert!(sout.contains("\n0x1f:  US: 1: 14.29%\n0x20:  SP: 0: 0.00%\n"));
+        assert!(sout.contains("\n0x61:   a: 2: 28.57%\n"));
+        assert!(sout.ends_with("\n0x7f: DEL: 0: 0.00%\n"));
+        assert_eq!(sout.lines().count(), 128);
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_nonzero() {
+        let (r, sioe) = do_execute!(["-l", "--map-ascii=nonzero"], "ab\x1fa\tb\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"1\"\n",
+                "0x09:  HT: 1: 14.29%\n",
+                "0x0a:  LF: 1: 14.29%\n",
+                "0x1f:  US: 1: 14.29%\n",
+                "0x61:   a: 2: 28.57%\n",
+                "0x62:   b: 2: 28.57%\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_nonzero_crlf() {
+        let (r, sioe) = do_execute!(["--map-ascii=nonzero"], "a\tb\r\nc\x1f\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "0x09:  HT: 1: 12.50%\n",
+                "0x0a:  LF: 2: 25.00%\n",
+                "0x0d:  CR: 1: 12.50%\n",
+                "0x1f:  US: 1: 12.50%\n",
+                "0x61:   a: 1: 12.50%\n",
+                "0x62:   b: 1: 12.50%\n",
+                "0x63:   c: 1: 12.50%\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_detailed_record_str() {
+        let (r, sioe) = do_execute!(["--map-ascii=detailed", "--record-str", ";"], "a;b\r\n");
+        assert_eq!(buff!(sioe, serr), "");
+        let sout = buff!(sioe, sout);
+        assert!(sout.contains("\n0x0a:  LF: 1: 20.00%\n"));
+        assert!(sout.contains("\n0x0d:  CR: 1: 20.00%\n"));
+        assert!(sout.contains("\n0x3b:   ;: 1: 20.00%\n"));
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_nonzero_empty() {
+        let (r, sioe) = do_execute!(["-l", "--map-ascii=nonzero"], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "lines:\"0\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_invalid_mode() {
+        let (r, sioe) = do_execute!(["--map-ascii=other"], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": Invalid option argument: map-ascii: can not parse 'other'\n",
+                "Missing option: b, c, l, w, a or another statistics option\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+fn table_vals(zero: &str, vals: &[(usize, &str)]) -> String {
+    let mut vec: Vec<&str> = vec![zero; 128];
+    for (i, v) in vals {
+        vec[*i] = v;
+    }
+    vec.join(", ")
+}
+
+mod test_5_emit_table_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_rust() {
+        let (r, sioe) = do_execute!(["--emit-table", "rust"], "aab\n");
+        assert_eq!(buff!(sioe, serr), "");
+        let vals = super::table_vals("0", &[(0x61, "255"), (0x62, "127")]);
+        assert_eq!(
+            buff!(sioe, sout),
+            format!("const ASCII_STOCHAS: [u8;128] = [{vals}];\n")
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_x_map_ascii_rust_src() {
+        let (r, sioe) = do_execute!(["--map-ascii", "-X", "map-ascii-rust-src"], "aab\n");
+        assert_eq!(buff!(sioe, serr), "");
+        let vals = super::table_vals("0", &[(0x61, "255"), (0x62, "127")]);
+        assert_eq!(
+            buff!(sioe, sout),
+            format!("const ASCII_STOCHAS: [u8;128] = [{vals}];\n")
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_x_map_ascii_rust_src_empty() {
+        let (r, sioe) = do_execute!(["--map-ascii", "-X", "map-ascii-rust-src"], "");
+        assert_eq!(buff!(sioe, serr), "");
+        let vals = super::table_vals("0", &[]);
+        assert_eq!(
+            buff!(sioe, sout),
+            format!("const ASCII_STOCHAS: [u8;128] = [{vals}];\n")
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_c_u16_sum() {
+        let (r, sioe) = do_execute!(
+            [
+                "--emit-table",
+                "c",
+                "--table-type",
+                "u16",
+                "--table-norm",
+                "sum",
+                "--table-name",
+                "freq"
+            ],
+            "aab\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        let vals = super::table_vals("0", &[(0x61, "43690"), (0x62, "21845")]);
+        assert_eq!(
+            buff!(sioe, sout),
+            format!("const uint16_t freq[128] = {{{vals}}};\n")
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_python_f32_sum() {
+        let (r, sioe) = do_execute!(
+            [
+                "--emit-table",
+                "python",
+                "--table-type",
+                "f32",
+                "--table-norm",
+                "sum"
+            ],
+            "aab\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        let vals = super::table_vals("0.0", &[(0x61, "0.6666667"), (0x62, "0.33333334")]);
+        assert_eq!(buff!(sioe, sout), format!("ASCII_STOCHAS = [{vals}]\n"));
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_json_f32_log() {
+        let (r, sioe) = do_execute!(
+            [
+                "--emit-table",
+                "json",
+                "--table-type",
+                "f32",
+                "--table-norm",
+                "log"
+            ],
+            "aab\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        let vals = super::table_vals("null", &[(0x61, "-0.4054651"), (0x62, "-1.0986123")]);
+        assert_eq!(
+            buff!(sioe, sout),
+            format!("{{\"ASCII_STOCHAS\": [{vals}]}}\n")
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_log_needs_f32() {
+        let (r, sioe) = do_execute!(["--emit-table", "c", "--table-norm", "log"], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": Invalid option argument: table-norm: 'log' needs the table type 'f32'\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+    //
+    #[test]
+    fn test_invalid_table_name() {
+        let (r, sioe) = do_execute!(["--emit-table", "c", "--table-name", "1a"], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": Invalid option argument: table-name: '1a' is not an identifier\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+mod test_5_map_bar_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_percent() {
+        let (r, sioe) = do_execute!(["--map-ascii", "--map-percent"], "aab\n");
+        assert_eq!(buff!(sioe, serr), "");
+        let sout = buff!(sioe, sout);
+        let lines: Vec<&str> = sout.lines().collect();
+        assert_eq!(lines.len(), 98);
+        assert_eq!(lines[0], "ctrl: --: 0: 0.00%");
+        assert_eq!(lines[3], "0x20: SP: 0: 0.00%");
+        assert_eq!(lines[68], "0x61:  a: 2: 66.67%");
+        assert_eq!(lines[69], "0x62:  b: 1: 33.33%");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_sort_count() {
+        let (r, sioe) = do_execute!(["--map-ascii=nonzero", "--map-sort", "count"], "abb\tc\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "0x62:   b: 2: 33.33%\n",
+                "0x09:  HT: 1: 16.67%\n",
+                "0x0a:  LF: 1: 16.67%\n",
+                "0x61:   a: 1: 16.67%\n",
+                "0x63:   c: 1: 16.67%\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_bar() {
+        let (r, sioe) = do_execute!(
+            ["--map-ascii=nonzero", "--map-bar", "--map-width", "36"],
+            "abb\tc\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "0x09:  HT: 1: 16.67%: ███████\n",
+                "0x0a:  LF: 1: 16.67%: ███████\n",
+                "0x61:   a: 1: 16.67%: ███████\n",
+                "0x62:   b: 2: 33.33%: ██████████████\n",
+                "0x63:   c: 1: 16.67%: ███████\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_bar_sort_count_plain() {
+        let (r, sioe) = do_execute!(
+            [
+                "--map-ascii",
+                "--map-bar",
+                "--map-sort",
+                "count",
+                "--map-width",
+                "20"
+            ],
+            "aab\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        let sout = buff!(sioe, sout);
+        let lines: Vec<&str> = sout.lines().collect();
+        assert_eq!(lines[0], "0x61:  a: 2: ██████████");
+        assert_eq!(lines[1], "0x62:  b: 1: █████");
+        assert_eq!(lines[2], "ctrl: --: 0");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_invalid_sort() {
+        let (r, sioe) = do_execute!(["--map-ascii", "--map-sort", "name"], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": Invalid option argument: map-sort: can not parse 'name'\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+const IN_DAT_PATTERN: &str = "foo bar foo\nbaz\nfoo.\n";
+
+mod test_5_pattern_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_patterns() {
+        let (r, sioe) = do_execute!(
+            ["-l", "-e", "foo", "--pattern", "ba[rz]"],
+            super::IN_DAT_PATTERN
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"3\", ",
+                "matches:foo:\"3\", matched-lines:foo:\"2\", ",
+                "matches:ba[rz]:\"2\", matched-lines:ba[rz]:\"2\"\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_pattern_only() {
+        let (r, sioe) = do_execute!(["-e", "qux"], super::IN_DAT_PATTERN);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "matches:qux:\"0\", matched-lines:qux:\"0\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_fixed_strings() {
+        let (r, sioe) = do_execute!(["-F", "-e", "o."], super::IN_DAT_PATTERN);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "matches:o.:\"1\", matched-lines:o.:\"1\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_with_record_sep() {
+        let (r, sioe) = do_execute!(
+            ["--record-sep", "\n\n", "-e", "foo"],
+            "foo\nfoo\n\nbar\n\nfoo\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "matches:foo:\"3\", matched-lines:foo:\"2\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_invalid_regex() {
+        let (r, sioe) = do_execute!(["-e", "("], super::IN_DAT_PATTERN);
+        assert!(buff!(sioe, serr).starts_with(concat!(program_name!(), ": regex parse error")));
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+const IN_DAT_DUP_LINES: &str = "b\na\nb\n A\nc\nb\na\n";
+
+mod test_5_dup_lines_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_dup_lines() {
+        let (r, sioe) = do_execute!(["--dup-lines"], super::IN_DAT_DUP_LINES);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "dup-lines: distinct: 4\n",
+                "dup-lines: duplicated: 2\n",
+                "dup-lines: top: 3: b\n",
+                "dup-lines: top: 2: a\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_trim_fold_top() {
+        let (r, sioe) = do_execute!(
+            ["--dup-lines", "--dup-trim", "--dup-fold", "--dup-top", "1"],
+            super::IN_DAT_DUP_LINES
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "dup-lines: distinct: 3\n",
+                "dup-lines: duplicated: 2\n",
+                "dup-lines: top: 3: a\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_with_lines() {
+        let (r, sioe) = do_execute!(["-l", "--dup-lines", "--dup-top", "0"], "x\nx\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"2\"\n",
+                "dup-lines: distinct: 1\n",
+                "dup-lines: duplicated: 1\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_repeated_lines() {
+        // as `uniq -d | wc -l`, not the all lines of them as `uniq -D | wc -l`
+        let (r, sioe) = do_execute!(["--dup-lines"], "a\na\na\nb\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "dup-lines: distinct: 2\n",
+                "dup-lines: duplicated: 1\n",
+                "dup-lines: top: 3: a\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_approx() {
+        let (r, sioe) = do_execute!(
+            ["--dup-lines", "--dup-approx", "100"],
+            super::IN_DAT_DUP_LINES
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "dup-lines: distinct: ~4\n",
+                "dup-lines: duplicated: ~2\n",
+                "dup-lines: top: ~3: b\n",
+                "dup-lines: top: ~2: a\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_approx_evicted() {
+        let (r, sioe) = do_execute!(
+            ["--dup-lines", "--dup-approx", "2"],
+            super::IN_DAT_DUP_LINES
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!("dup-lines: distinct: ~4\n", "dup-lines: duplicated: ~0\n",)
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_approx_zero() {
+        let (r, sioe) = do_execute!(["--dup-lines", "--dup-approx", "0"], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": Invalid option argument: dup-approx: needs one or more counters\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+mod test_5_hash_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_sha256() {
+        let (r, sioe) = do_execute!(["--hash", "sha256"], "abc");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "sha256:\"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_blake3() {
+        let (r, sioe) = do_execute!(["--hash", "blake3"], "abc");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "blake3:\"6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_crc32() {
+        let (r, sioe) = do_execute!(["--hash", "crc32"], "abc");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "crc32:\"352441c2\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_xxh3() {
+        let (r, sioe) = do_execute!(["--hash", "xxh3"], "abc");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "xxh3:\"78af5f94892f3950\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_exact_bytes() {
+        // the newlines and the carriage returns are hashed as is.
+        let (r, sioe) = do_execute!(["-l", "-b", "--hash", "crc32"], "abc\r\nde");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "lines:\"2\", bytes:\"5\", crc32:\"e8b75d9c\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_with_record_sep() {
+        let (r, sioe) = do_execute!(["--record-sep", ",", "--hash", "crc32"], "abc\r\nde");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "crc32:\"e8b75d9c\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_invalid_alg() {
+        let (r, sioe) = do_execute!(["--hash", "md5"], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": Invalid option argument: hash: can not parse 'md5'\n",
+                "Missing option: b, c, l, w, a or another statistics option\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+const IN_DAT_FIELDS: &str =
+    "id,name,note\n1,\"Smith, J\",\"a \"\"q\"\"\"\n2,,\"multi\nline\"\n3,x\n\n";
+
+mod test_5_fields_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_csv() {
+        let (r, sioe) = do_execute!(["-l", "--fields"], super::IN_DAT_FIELDS);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"6\"\n",
+                "fields: rows: 4\n",
+                "fields: consistent: no\n",
+                "fields: field-count: 2: 1\n",
+                "fields: field-count: 3: 3\n",
+                "fields: column: 1: non-empty: 4, distinct: 4, width: 1..2\n",
+                "fields: column: 2: non-empty: 3, distinct: 4, width: 0..8\n",
+                "fields: column: 3: non-empty: 3, distinct: 3, width: 4..10\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_tab() {
+        let (r, sioe) = do_execute!(["--fields", "-t"], "a\tb\n\tc\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "fields: rows: 2\n",
+                "fields: consistent: yes\n",
+                "fields: field-count: 2: 2\n",
+                "fields: column: 1: non-empty: 1, distinct: 2, width: 0..1\n",
+                "fields: column: 2: non-empty: 2, distinct: 2, width: 1..1\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_delimiter() {
+        let (r, sioe) = do_execute!(["--fields", "-d", ";"], "a;b,c\na;d\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "fields: rows: 2\n",
+                "fields: consistent: yes\n",
+                "fields: field-count: 2: 2\n",
+                "fields: column: 1: non-empty: 2, distinct: 1, width: 1..1\n",
+                "fields: column: 2: non-empty: 2, distinct: 2, width: 1..3\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_unterminated_quote() {
+        let (r, sioe) = do_execute!(["--fields"], "a,\"b\nc\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "fields: rows: 1\n",
+                "fields: consistent: yes\n",
+                "fields: field-count: 2: 1\n",
+                "fields: column: 1: non-empty: 1, distinct: 1, width: 1..1\n",
+                "fields: column: 2: non-empty: 1, distinct: 1, width: 3..3\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_invalid_delimiter() {
+        let (r, sioe) = do_execute!(["--fields", "-d", ";;"], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": Invalid option argument: delimiter: ';;' is not a character\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+const IN_DAT_NUMERIC: &str = "name,val\na,1000\nb,2500.5\nc,x\nd,-3\ne\n";
+
+mod test_5_numeric_field_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_numeric_field() {
+        let (r, sioe) = do_execute!(["--numeric-field", "2"], super::IN_DAT_NUMERIC);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "numeric: count: 3\n",
+                "numeric: skipped: 3\n",
+                "numeric: sum: 3497.5\n",
+                "numeric: min: -3\n",
+                "numeric: max: 2500.5\n",
+                "numeric: mean: 1165.833333\n",
+                "numeric: stddev: 1028.754452\n",
+                "numeric: p50: 1000\n",
+                "numeric: p90: 2200.4\n",
+                "numeric: p99: 2470.49\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_with_locale() {
+        let (r, sioe) = do_execute!(
+            ["--numeric-field", "2", "--locale", "en"],
+            super::IN_DAT_NUMERIC
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        let sout = buff!(sioe, sout);
+        assert!(sout.contains("numeric: sum: 3,497.5\n"));
+        assert!(sout.contains("numeric: max: 2,500.5\n"));
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_with_fields_tab() {
+        let (r, sioe) = do_execute!(["--fields", "-t", "--numeric-field", "1"], "1\ta\n3\tb\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "fields: rows: 2\n",
+                "fields: consistent: yes\n",
+                "fields: field-count: 2: 2\n",
+                "fields: column: 1: non-empty: 2, distinct: 2, width: 1..1\n",
+                "fields: column: 2: non-empty: 2, distinct: 2, width: 1..1\n",
+                "numeric: count: 2\n",
+                "numeric: skipped: 0\n",
+                "numeric: sum: 4\n",
+                "numeric: min: 1\n",
+                "numeric: max: 3\n",
+                "numeric: mean: 2\n",
+                "numeric: stddev: 1\n",
+                "numeric: p50: 2\n",
+                "numeric: p90: 2.8\n",
+                "numeric: p99: 2.98\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_no_numbers() {
+        let (r, sioe) = do_execute!(["--numeric-field", "3"], super::IN_DAT_NUMERIC);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "numeric: count: 0\nnumeric: skipped: 6\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_field_zero() {
+        let (r, sioe) = do_execute!(["--numeric-field", "0"], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": Invalid option argument: numeric-field: the field number starts from 1\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+const IN_DAT_CODE_RUST: &str = "\
+// line comment
+fn f<'a>(s: &'a str) -> char {
+    /* block
+       /* nested */
+       still */
+    let q = '\"'; // quote char
+
+    let s = \"multi
+// not a comment
+\";
+    '\\n' /* trailing */
+}
+";
+
+mod test_5_code_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_rust() {
+        let (r, sioe) = do_execute!(["-l", "--code", "rust"], super::IN_DAT_CODE_RUST);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "lines:\"12\", code:\"7\", comments:\"4\", blanks:\"1\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_c() {
+        let (r, sioe) = do_execute!(
+            ["--code", "c"],
+            "int a; /* c */\n/*\n*/ int b;\nchar c = '\"';\n// x\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "code:\"3\", comments:\"2\", blanks:\"0\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_python() {
+        let (r, sioe) = do_execute!(
+            ["--code", "python"],
+            "#!/usr/bin/env python3\n\"\"\"doc\nstring\"\"\"\n\nx = '#not comment'  # comment\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "code:\"3\", comments:\"1\", blanks:\"1\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_shell() {
+        let (r, sioe) = do_execute!(
+            ["--code", "shell"],
+            "echo ${#a} # c\n# full\n\necho \"a\nb\"\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "code:\"3\", comments:\"1\", blanks:\"1\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_toml() {
+        let (r, sioe) = do_execute!(
+            ["--code", "toml"],
+            "a = 1 # c\n# c\n[t]\ns = \"\"\"x\n# y\n\"\"\"\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "code:\"5\", comments:\"1\", blanks:\"0\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_rust_raw_string() {
+        let (r, sioe) = do_execute!(
+            ["--code", "rust"],
+            "let s = r\"C:\\\";\n// c\nlet t = r#\"a \"q\" // x\"#;\nlet u = br##\"\n\"#\n\"##; // c\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "code:\"5\", comments:\"1\", blanks:\"0\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_auto() {
+        let dir = format!("{}/test_code_auto_l", env!("CARGO_TARGET_TMPDIR"));
+        std::fs::create_dir_all(&dir).unwrap();
+        let rs = format!("{dir}/a.rs");
+        let py = format!("{dir}/b.py");
+        let txt = format!("{dir}/c.txt");
+        std::fs::write(&rs, "// c\nfn f() {}\n").unwrap();
+        std::fs::write(&py, "# c\n\nx = 1 // 2\n").unwrap();
+        std::fs::write(&txt, "# text\n").unwrap();
+        for (path, expected) in [
+            (&rs, "code:\"1\", comments:\"1\", blanks:\"0\""),
+            (&py, "code:\"1\", comments:\"1\", blanks:\"1\""),
+        ] {
+            let (r, sioe) = do_execute!(["-l", "--code", "auto", path], "");
+            assert_eq!(buff!(sioe, serr), "");
+            let lines = if path == &rs { 2 } else { 3 };
+            assert_eq!(
+                buff!(sioe, sout),
+                format!("lines:\"{lines}\", {expected}, file:\"{path}\"\n")
+            );
+            assert!(r.is_ok());
+        }
+        // the file of an unknown language has the code labels of "-".
+        let (r, sioe) = do_execute!(["-l", "--code", "auto", &txt], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            format!("lines:\"1\", code:\"-\", comments:\"-\", blanks:\"-\", file:\"{txt}\"\n")
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_auto_stdin() {
+        let (r, sioe) = do_execute!(["-l", "--code", "auto"], "fn f() {}\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "lines:\"1\", code:\"-\", comments:\"-\", blanks:\"-\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_invalid_lang() {
+        let (r, sioe) = do_execute!(["--code", "cobol"], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": Invalid option argument: code: can not parse 'cobol'\n",
+                "Missing option: b, c, l, w, a or another statistics option\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+const IN_DAT_MARKDOWN: &str = "\
+# Title
+
+Some *prose* with a [link](http://x) and ![img](a.png).
+
+Sub
+---
+
+- item one
+- item two
+1. first
+
+```rust
+let x = 1;
+// not words
+```
+
+> ## quoted heading
+";
+
+mod test_5_markdown_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_markdown() {
+        let (r, sioe) = do_execute!(["--markdown"], super::IN_DAT_MARKDOWN);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "markdown: heading: h1: 1\n",
+                "markdown: heading: h2: 2\n",
+                "markdown: heading: h3: 0\n",
+                "markdown: heading: h4: 0\n",
+                "markdown: heading: h5: 0\n",
+                "markdown: heading: h6: 0\n",
+                "markdown: code-blocks: 1\n",
+                "markdown: code-lines: 2\n",
+                "markdown: links: 1\n",
+                "markdown: images: 1\n",
+                "markdown: list-items: 3\n",
+                "markdown: words: 16\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_with_words() {
+        let (r, sioe) = do_execute!(["-w", "--markdown"], "a b\n~~~~\nc d e\n```\nf\n~~~~\n");
+        assert_eq!(buff!(sioe, serr), "");
+        let sout = buff!(sioe, sout);
+        assert!(sout.starts_with("words:\"9\"\n"));
+        assert!(sout.contains("markdown: code-blocks: 1\n"));
+        assert!(sout.contains("markdown: code-lines: 3\n"));
+        assert!(sout.ends_with("markdown: words: 2\n"));
+        assert!(r.is_ok());
+    }
+}
+
+const IN_DAT_LOG: &str = "\
+2024-01-02T03:04:05Z INFO start
+2024-01-02 03:59:00 WARNING disk
+  at stack frame
+2024-01-02 04:00:01 ERROR failed
+127.0.0.1 - - [02/Jan/2024:04:10:00 +0000] \"GET / HTTP/1.1\" 200 12
+Jan  2 05:00:00 host sshd[1]: DEBUG accepted
+";
+
+mod test_5_log_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_log() {
+        let (r, sioe) = do_execute!(["--log"], super::IN_DAT_LOG);
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "log: ERROR: 1\n",
+                "log: WARN: 1\n",
+                "log: INFO: 1\n",
+                "log: DEBUG: 1\n",
+                "log: other: 2\n",
+                "log: first: 2024-01-02 03:04:05\n",
+                "log: last: 01-02 05:00:00\n",
+                "log: per-hour: 01-02 05: 1\n",
+                "log: per-hour: 2024-01-02 03: 2\n",
+                "log: per-hour: 2024-01-02 04: 2\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_bucket_minute() {
+        let (r, sioe) = do_execute!(
+            ["-l", "--log", "--log-bucket", "minute"],
+            "2024-01-02 03:04:05 INFO a\n2024-01-02 03:04:59 INFO b\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"2\"\n",
+                "log: ERROR: 0\n",
+                "log: WARN: 0\n",
+                "log: INFO: 2\n",
+                "log: DEBUG: 0\n",
+                "log: other: 0\n",
+                "log: first: 2024-01-02 03:04:05\n",
+                "log: last: 2024-01-02 03:04:59\n",
+                "log: per-minute: 2024-01-02 03:04: 2\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_no_timestamp() {
+        let (r, sioe) = do_execute!(["--log"], "error: lower case\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "log: ERROR: 0\n",
+                "log: WARN: 0\n",
+                "log: INFO: 0\n",
+                "log: DEBUG: 0\n",
+                "log: other: 1\n",
+                "log: first: -\n",
+                "log: last: -\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+}
+
+mod test_5_files_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    pub(super) fn make_large_file(name: &str) -> String {
+        let path = format!("{}/{name}", env!("CARGO_TARGET_TMPDIR"));
+        let mut s = String::new();
+        for i in 0..20000 {
+            s.push_str(&format!("line {i} {}\n", "ab ".repeat(i % 17)));
+            if i % 100 == 0 {
+                s.push_str("\n  \nend \u{3042}\u{3044} \n");
+            }
+            if i % 50 == 0 {
+                s.push_str("crlf \t\r\n\r\n");
+            }
+        }
+        s.push_str("no newline\r");
+        std::fs::write(&path, s).unwrap();
+        path
+    }
+    //
+    #[test]
+    fn test_one_file() {
+        let (r, sioe) = do_execute!(["-l", "-w", fixture_sample_text!()], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "lines:\"10\", words:\"10\", file:\"fixtures/sample-text.txt\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_two_files() {
+        let (r, sioe) = do_execute!(
+            ["-l", "-b", fixture_sample_text!(), fixture_sherlock!()],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"10\", bytes:\"120\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"26\", bytes:\"1207\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"36\", bytes:\"1327\", file:\"total\"\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_two_files_code() {
+        let (r, sioe) = do_execute!(
+            [
+                "-l",
+                "--code",
+                "python",
+                fixture_sample_text!(),
+                fixture_sherlock!()
+            ],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"10\", code:\"10\", comments:\"0\", blanks:\"0\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"26\", code:\"24\", comments:\"0\", blanks:\"2\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"36\", code:\"34\", comments:\"0\", blanks:\"2\", file:\"total\"\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_two_files_patterns() {
+        let (r, sioe) = do_execute!(
+            [
+                "-e",
+                "text",
+                "-e",
+                "the",
+                fixture_sample_text!(),
+                fixture_sherlock!()
+            ],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "matches:text:\"10\", matched-lines:text:\"10\", ",
+                "matches:the:\"0\", matched-lines:the:\"0\", file:\"fixtures/sample-text.txt\"\n",
+                "matches:text:\"0\", matched-lines:text:\"0\", ",
+                "matches:the:\"13\", matched-lines:the:\"11\", file:\"fixtures/sherlock.txt\"\n",
+                "matches:text:\"10\", matched-lines:text:\"10\", ",
+                "matches:the:\"13\", matched-lines:the:\"11\", file:\"total\"\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_stdin_dash() {
+        let (r, sioe) = do_execute!(
+            ["-l", fixture_sherlock!(), "-", fixture_sample_text!(), "-"],
+            "a\nb\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"26\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"2\", file:\"-\"\n",
+                "lines:\"10\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"0\", file:\"-\"\n",
+                "lines:\"38\", file:\"total\"\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_stdin_dash_only() {
+        let (r, sioe) = do_execute!(["-l", "-e", "-", "-"], "a-b\nc\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "lines:\"2\", matches:-:\"1\", matched-lines:-:\"1\", file:\"-\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_not_found() {
+        let (r, sioe) = do_execute!(["-l", "fixtures/not-found.txt"], "");
+        assert!(
+            buff!(sioe, serr).starts_with(concat!(program_name!(), ": fixtures/not-found.txt: "))
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+    //
+    #[test]
+    fn test_error_continues() {
+        let (r, sioe) = do_execute!(
+            [
+                "-l",
+                fixture_sample_text!(),
+                "fixtures/not-found.txt",
+                fixture_sherlock!()
+            ],
+            ""
+        );
+        let serr = buff!(sioe, serr);
+        let lines: Vec<&str> = serr.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(concat!(program_name!(), ": fixtures/not-found.txt: ")));
+        assert_eq!(
+            lines[1],
+            concat!(program_name!(), ": 1 of 3 files could not be processed")
+        );
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"10\", file:\"fixtures/sample-text.txt\"\n",
+                "lines:\"26\", file:\"fixtures/sherlock.txt\"\n",
+                "lines:\"36\", file:\"total\"\n",
+            )
+        );
+        assert!(r.is_err());
+    }
+    //
+    #[test]
+    fn test_threads_files() {
+        let mut args = vec!["-a", "--dup-lines", "--threads", "1"];
+        for _ in 0..10 {
+            args.push(fixture_sample_text!());
+            args.push(fixture_sherlock!());
+            args.push(fixture_invalid_utf8!());
+        }
+        let (_r, sioe) = do_execute!(&args, "");
+        let expected_out = buff!(sioe, sout);
+        let expected_err = buff!(sioe, serr);
+        for threads in ["0", "2", "4", "32"] {
+            args[3] = threads;
+            let (_r, sioe) = do_execute!(&args, "");
+            assert_eq!(buff!(sioe, sout), expected_out, "threads: {threads}");
+            assert_eq!(buff!(sioe, serr), expected_err, "threads: {threads}");
+        }
+    }
+    //
+    #[test]
+    fn test_threads() {
+        let path = make_large_file("test_threads_l.txt");
+        let (r, sioe) = do_execute!(["-a", &path], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert!(r.is_ok());
+        let expected = buff!(sioe, sout);
+        for threads in ["0", "2", "4", "7", "32"] {
+            let (r, sioe) = do_execute!(["-a", "--threads", threads, &path], "");
+            assert_eq!(buff!(sioe, serr), "");
+            assert_eq!(buff!(sioe, sout), expected, "threads: {threads}");
+            assert!(r.is_ok());
+        }
+    }
+}
+
+mod test_5_mmap_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_mmap() {
+        let path = super::test_5_files_l::make_large_file("test_mmap_l.txt");
+        let (r, sioe) = do_execute!(["-a", "--blank", "--ws-only", "--trailing-ws", &path], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert!(r.is_ok());
+        let expected = buff!(sioe, sout);
+        for threads in ["1", "4", "32"] {
+            let (r, sioe) = do_execute!(
+                [
+                    "-a",
+                    "--blank",
+                    "--ws-only",
+                    "--trailing-ws",
+                    "--mmap",
+                    "--threads",
+                    threads,
+                    &path
+                ],
+                ""
+            );
+            assert_eq!(buff!(sioe, serr), "");
+            assert_eq!(buff!(sioe, sout), expected, "threads: {threads}");
+            assert!(r.is_ok());
+        }
+    }
+    //
+    #[test]
+    fn test_mmap_extra() {
+        let (_r, sioe) = do_execute!(["-l", "--dup-lines", fixture_sample_text!()], "");
+        let expected = buff!(sioe, sout);
+        let (r, sioe) = do_execute!(["-l", "--dup-lines", "--mmap", fixture_sample_text!()], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), expected);
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_mmap_invalid_utf8() {
+        let (r, sioe) = do_execute!(["-l", "--mmap", fixture_invalid_utf8!()], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": fixtures/invalid_utf8.txt: stream did not contain valid UTF-8\n"
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+#[cfg(feature = "decompress")]
+mod test_5_decompress_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    macro_rules! expected {
+        ($compressed:expr, $ratio:expr, $path:expr) => {
+            concat!(
+                "lines:\"26\", bytes:\"1207\", chars:\"1207\", words:\"226\", max:\"83\", ",
+                "compressed:\"",
+                $compressed,
+                "\", ratio:\"",
+                $ratio,
+                "\", file:\"",
+                $path,
+                "\"\n"
+            )
+        };
+    }
+    //
+    #[test]
+    fn test_gzip() {
+        let (r, sioe) = do_execute!(
+            ["-a", "--decompress", "--compressed", fixture_sherlock_gz!()],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            expected!("700", "1.76", fixture_sherlock_gz!())
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_zstd() {
+        let (r, sioe) = do_execute!(
+            [
+                "-a",
+                "--decompress",
+                "--compressed",
+                fixture_sherlock_zst!()
+            ],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            expected!("702", "1.76", fixture_sherlock_zst!())
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_zstd_multi_frame() {
+        // the frames of "a\nb\n" and "c\n" with a skippable frame between them
+        let (r, sioe) = do_execute!(["-l", "-b", "--decompress", fixture_multi_frame_zst!()], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"3\", bytes:\"3\", file:\"",
+                fixture_multi_frame_zst!(),
+                "\"\n"
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_xz() {
+        let (r, sioe) = do_execute!(
+            ["-a", "--decompress", "--compressed", fixture_sherlock_xz!()],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            expected!("836", "1.47", fixture_sherlock_xz!())
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_bzip2() {
+        let (r, sioe) = do_execute!(
+            [
+                "-a",
+                "--decompress",
+                "--compressed",
+                fixture_sherlock_bz2!()
+            ],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            expected!("706", "1.75", fixture_sherlock_bz2!())
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_bzip2_like_text() {
+        // "BZh" without the block size and the block magic is a text.
+        let (r, sioe) = do_execute!(["-l", "--decompress", "--compressed"], "BZhello\nworld\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "lines:\"2\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_not_compressed() {
+        let (r, sioe) = do_execute!(["-l", "--decompress", "--compressed"], "abc\ndef\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "lines:\"2\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_hash() {
+        // the digest of the compressed input bytes, as `sha256sum`
+        let (r, sioe) = do_execute!(
+            [
+                "-l",
+                "--hash",
+                "sha256",
+                "--decompress",
+                fixture_sherlock_gz!()
+            ],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"26\", ",
+                "sha256:\"8764baad040f0c7a7a7992436647ea2b064c1418958680300b2223987e9f7c35\", ",
+                "file:\"fixtures/sherlock.txt.gz\"\n"
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_mmap() {
+        let (r, sioe) = do_execute!(["-l", "--decompress", "--mmap", fixture_sherlock_xz!()], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            "lines:\"26\", file:\"fixtures/sherlock.txt.xz\"\n"
+        );
+        assert!(r.is_ok());
+    }
+}
+
+mod test_5_compressed_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_without_decompress() {
+        let (r, sioe) = do_execute!(["-l", "--compressed"], "abc\n");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": ",
+                "Invalid option: --compressed: needs --decompress\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+#[cfg(not(feature = "decompress"))]
+mod test_5_no_decompress_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_no_feature() {
+        let (r, sioe) = do_execute!(["-l", "--decompress"], "abc\n");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": ",
+                "Invalid option: --decompress: needs the `decompress` feature of the build\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+mod test_5_recursive_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    pub(super) fn make_tree(name: &str) -> String {
+        let root = format!("{}/{name}", env!("CARGO_TARGET_TMPDIR"));
+        let _ = std::fs::remove_dir_all(&root);
+        for (path, content) in [
+            (".gitignore", "target/\n*.log\n"),
+            ("src/a.rs", "a\nb\n"),
+            ("src/sub/b.rs", "c\n"),
+            ("src/c.txt", "d\ne\nf\n"),
+            ("src/bin.dat", "bin\0ary\n"),
+            ("target/t.rs", "g\n"),
+            ("x.log", "h\n"),
+            (".git/HEAD", "ref\n"),
+        ] {
+            let path = format!("{root}/{path}");
+            std::fs::create_dir_all(std::path::Path::new(&path).parent().unwrap()).unwrap();
+            std::fs::write(&path, content).unwrap();
+        }
+        root
+    }
+    //
+    fn expected(root: &str, rows: &[(&str, &str)]) -> String {
+        let mut s = String::new();
+        for (path, lines) in rows {
+            if *path == "total" {
+                s.push_str(&format!("lines:\"{lines}\", file:\"total\"\n"));
+            } else {
+                s.push_str(&format!("lines:\"{lines}\", file:\"{root}/{path}\"\n"));
+            }
+        }
+        s
+    }
+    //
+    #[test]
+    fn test_recursive() {
+        let root = make_tree("test_recursive_l");
+        let (r, sioe) = do_execute!(["-l", "-r", &root], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            expected(
+                &root,
+                &[
+                    (".gitignore", "2"),
+                    ("src/a.rs", "2"),
+                    ("src/c.txt", "3"),
+                    ("src/sub/b.rs", "1"),
+                    ("target/t.rs", "1"),
+                    ("x.log", "1"),
+                    ("total", "10"),
+                ]
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_include_exclude() {
+        let root = make_tree("test_include_exclude_l");
+        let (r, sioe) = do_execute!(
+            [
+                "-l",
+                "-r",
+                "--include",
+                "*.rs",
+                "--exclude",
+                "target/**",
+                "--threads",
+                "4",
+                &root
+            ],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            expected(
+                &root,
+                &[("src/a.rs", "2"), ("src/sub/b.rs", "1"), ("total", "3")]
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_gitignore() {
+        let root = make_tree("test_gitignore_l");
+        let (r, sioe) = do_execute!(["-l", "-r", "--gitignore", &root], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            expected(
+                &root,
+                &[
+                    (".gitignore", "2"),
+                    ("src/a.rs", "2"),
+                    ("src/c.txt", "3"),
+                    ("src/sub/b.rs", "1"),
+                    ("total", "8"),
+                ]
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_not_found() {
+        let (r, sioe) = do_execute!(["-l", "-r", "fixtures/not-found"], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": fixtures/not-found: ",
+                "No such file or directory (os error 2)\n",
+                program_name!(),
+                ": 1 of 1 files could not be processed\n",
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "lines:\"0\", file:\"total\"\n");
+        assert!(r.is_err());
+    }
+}
+
+mod test_5_files_from_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    const OUT_DAT: &str = concat!(
+        "lines:\"10\", file:\"fixtures/sample-text.txt\"\n",
+        "lines:\"26\", file:\"fixtures/sherlock.txt\"\n",
+        "lines:\"36\", file:\"total\"\n",
+    );
+    //
+    #[test]
+    fn test_files_from_stdin() {
+        let (r, sioe) = do_execute!(
+            ["-l", "--files-from", "-"],
+            "fixtures/sample-text.txt\r\n\nfixtures/sherlock.txt\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), OUT_DAT);
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_files0_from_stdin() {
+        let (r, sioe) = do_execute!(
+            ["-l", "--files0-from", "-"],
+            "fixtures/sample-text.txt\0fixtures/sherlock.txt\0"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), OUT_DAT);
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_files_from_file() {
+        let path = format!("{}/test_files_from_l.txt", env!("CARGO_TARGET_TMPDIR"));
+        std::fs::write(&path, "fixtures/sherlock.txt\n").unwrap();
+        let (r, sioe) = do_execute!(["-l", fixture_sample_text!(), "--files-from", &path], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), OUT_DAT);
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_files_from_empty() {
+        let (r, sioe) = do_execute!(["-l", "--files-from", "-"], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "lines:\"0\", file:\"total\"\n");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_files_from_not_found() {
+        let (r, sioe) = do_execute!(["-l", "--files-from", "fixtures/not-found.txt"], "");
+        assert!(
+            buff!(sioe, serr).starts_with(concat!(program_name!(), ": fixtures/not-found.txt: "))
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+mod test_5_base_dir_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    #[test]
+    fn test_file() {
+        let (r, sioe) = do_execute!(["-l", "-X", "base_dir=fixtures", "sample-text.txt"], "");
+        assert_eq!(buff!(sioe, serr), "");
+        // the path is output as given, not as opened.
+        assert_eq!(
+            buff!(sioe, sout),
+            "lines:\"10\", file:\"sample-text.txt\"\n"
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_absolute() {
+        let path = format!("{}/{}", env!("CARGO_MANIFEST_DIR"), fixture_sherlock!());
+        let (r, sioe) = do_execute!(["-l", "-X", "base_dir=src", &path], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            format!("lines:\"26\", file:\"{path}\"\n")
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_files_from() {
+        let (r, sioe) = do_execute!(
+            ["-l", "-X", "base_dir=fixtures", "--files-from", "-"],
+            "sample-text.txt\nsherlock.txt\n"
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"10\", file:\"sample-text.txt\"\n",
+                "lines:\"26\", file:\"sherlock.txt\"\n",
+                "lines:\"36\", file:\"total\"\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_recursive() {
+        let root = super::test_5_recursive_l::make_tree("test_base_dir_recursive_l");
+        let (r, sioe) = do_execute!(["-l", "-r", "-X", &format!("base_dir={root}"), "src"], "");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"2\", file:\"src/a.rs\"\n",
+                "lines:\"3\", file:\"src/c.txt\"\n",
+                "lines:\"1\", file:\"src/sub/b.rs\"\n",
+                "lines:\"6\", file:\"total\"\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_group_by_dir() {
+        let (r, sioe) = do_execute!(
+            [
+                "-l",
+                "-X",
+                "base_dir=fixtures",
+                "--group-by",
+                "dir",
+                "sample-text.txt",
+                "sherlock.txt"
+            ],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(
+            buff!(sioe, sout),
+            concat!(
+                "lines:\"10\", file:\"sample-text.txt\"\n",
+                "lines:\"26\", file:\"sherlock.txt\"\n",
+                "lines:\"36\", group:\".\"\n",
+                "lines:\"36\", file:\"total\"\n",
+            )
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_stdin() {
+        let (r, sioe) = do_execute!(["-l", "-X", "base_dir=fixtures"], "abc\n");
+        assert_eq!(buff!(sioe, serr), "");
+        assert_eq!(buff!(sioe, sout), "lines:\"1\"\n");
+        assert!(r.is_ok());
+    }
+}
+
+mod test_5_group_by_l {
+    use libaki_stats::*;
+    use runnel::medium::stringio::{StringErr, StringIn, StringOut};
+    use runnel::RunnelIoe;
+    //
+    fn group_rows(out: &str) -> Vec<&str> {
+        out.lines().filter(|line| line.contains("group:")).collect()
+    }
+    //
+    #[test]
+    fn test_ext() {
+        let root = super::test_5_recursive_l::make_tree("test_group_ext_l");
+        let (r, sioe) = do_execute!(["-l", "-r", "--group-by", "ext", &root], "");
+        assert_eq!(buff!(sioe, serr), "");
+        let out = buff!(sioe, sout);
+        assert_eq!(
+            group_rows(&out),
+            [
+                "lines:\"2\", group:\"(none)\"",
+                "lines:\"1\", group:\".log\"",
+                "lines:\"4\", group:\".rs\"",
+                "lines:\"3\", group:\".txt\"",
+            ]
+        );
+        assert!(out.ends_with("lines:\"10\", file:\"total\"\n"));
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_ext_with_code_patterns() {
+        let root = super::test_5_recursive_l::make_tree("test_group_ext_code_l");
+        let (r, sioe) = do_execute!(
+            [
+                "-l",
+                "-r",
+                "--code",
+                "auto",
+                "-e",
+                "b",
+                "--group-by",
+                "ext",
+                &format!("{root}/src")
+            ],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        let out = buff!(sioe, sout);
+        assert_eq!(
+            group_rows(&out),
+            [
+                concat!(
+                    "lines:\"3\", code:\"3\", comments:\"0\", blanks:\"0\", ",
+                    "matches:b:\"1\", matched-lines:b:\"1\", group:\".rs\""
+                ),
+                concat!(
+                    "lines:\"3\", code:\"-\", comments:\"-\", blanks:\"-\", ",
+                    "matches:b:\"0\", matched-lines:b:\"0\", group:\".txt\""
+                ),
+            ]
+        );
+        assert!(out.ends_with(concat!(
+            "lines:\"6\", code:\"3\", comments:\"0\", blanks:\"0\", ",
+            "matches:b:\"1\", matched-lines:b:\"1\", file:\"total\"\n"
+        )));
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_dir() {
+        let (r, sioe) = do_execute!(
+            [
+                "-l",
+                "--group-by",
+                "dir",
+                fixture_sample_text!(),
+                fixture_sherlock!(),
+                "Cargo.toml"
+            ],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        let out = buff!(sioe, sout);
+        let rows = group_rows(&out);
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].ends_with(", group:\".\""));
+        assert_eq!(rows[1], "lines:\"36\", group:\"fixtures\"");
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_depth() {
+        // the depth is counted from the root directory of -r.
+        let root = super::test_5_recursive_l::make_tree("test_group_depth_l");
+        let (r, sioe) = do_execute!(["-l", "-r", "--group-by", "depth=1", &root], "");
+        assert_eq!(buff!(sioe, serr), "");
+        let out = buff!(sioe, sout);
+        assert_eq!(
+            group_rows(&out),
+            [
+                format!("lines:\"3\", group:\"{root}\""),
+                format!("lines:\"6\", group:\"{root}/src\""),
+                format!("lines:\"1\", group:\"{root}/target\""),
+            ]
+        );
+        assert!(r.is_ok());
+        //
+        let (r, sioe) = do_execute!(["-l", "-r", "--group-by", "depth=2", &root], "");
+        assert_eq!(buff!(sioe, serr), "");
+        let out = buff!(sioe, sout);
+        assert_eq!(
+            group_rows(&out),
+            [
+                format!("lines:\"3\", group:\"{root}\""),
+                format!("lines:\"5\", group:\"{root}/src\""),
+                format!("lines:\"1\", group:\"{root}/src/sub\""),
+                format!("lines:\"1\", group:\"{root}/target\""),
+            ]
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_depth_files() {
+        // the depth of the files as given does not count the root directory.
+        let root = super::test_5_recursive_l::make_tree("test_group_depth_files_l");
+        let a_rs = format!("{root}/src/a.rs");
+        let (r, sioe) = do_execute!(
+            [
+                "-l",
+                "--group-by",
+                "depth=1",
+                &a_rs,
+                fixture_sample_text!(),
+                fixture_sherlock!()
+            ],
+            ""
+        );
+        assert_eq!(buff!(sioe, serr), "");
+        let out = buff!(sioe, sout);
+        let top = std::path::Path::new(&root)
+            .ancestors()
+            .filter(|dir| dir.parent().is_some())
+            .last()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+        assert_eq!(
+            group_rows(&out),
+            [
+                format!("lines:\"2\", group:\"{top}\""),
+                "lines:\"36\", group:\"fixtures\"".to_string(),
+            ]
+        );
+        assert!(r.is_ok());
+    }
+    //
+    #[test]
+    fn test_invalid_key() {
+        let (r, sioe) = do_execute!(["-l", "--group-by", "depth=0"], "");
+        assert_eq!(
+            buff!(sioe, serr),
+            concat!(
+                program_name!(),
+                ": Invalid option argument: group-by: can not parse 'depth=0'\n",
+                try_help_msg!()
+            )
+        );
+        assert_eq!(buff!(sioe, sout), "");
+        assert!(r.is_err());
+    }
+}
+
+mod test_6_compute_l {
+    use libaki_stats::{compute, Stats, StatsConfig};
+    //
+    #[test]
+    fn test_default() {
+        let stats = compute("abc def\n\n  \nghi \n".as_bytes(), &StatsConfig::default()).unwrap();
+        assert_eq!(stats.byte_count, 13);
+        assert_eq!(stats.char_count, 13);
+        assert_eq!(stats.line_count, 4);
+        assert_eq!(stats.word_count, 3);
+        assert_eq!(stats.max_line_bytes, 7);
+        assert_eq!(stats.blank_count, 1);
+        assert_eq!(stats.ws_only_count, 1);
+        assert_eq!(stats.trailing_ws_count, 2);
+    }
+    //
+    #[test]
+    fn test_empty() {
+        let stats = compute("".as_bytes(), &StatsConfig::default()).unwrap();
+        assert_eq!(stats, Stats::default());
+    }
+    //
+    #[test]
+    fn test_word_delims() {
+        let config = StatsConfig::builder().word_delims(",;").build().unwrap();
+        let stats = compute("a,b;c d\n".as_bytes(), &config).unwrap();
+        assert_eq!(stats.word_count, 4);
+    }
+    //
+    #[test]
+    fn test_record_sep() {
+        let config = StatsConfig::builder().record_sep("\n\n").build().unwrap();
+        let stats = compute("a\nb\n\nc\n".as_bytes(), &config).unwrap();
+        assert_eq!(stats.line_count, 2);
+        assert_eq!(stats.word_count, 3);
+    }
+    //
+    #[test]
+    fn test_invalid_record_sep() {
+        let r = StatsConfig::builder().record_sep("(").build();
+        assert!(r.is_err());
+    }
+    //
+    #[test]
+    fn test_record_str() {
+        let config = StatsConfig::builder().record_str("\n\n").build().unwrap();
+        let stats = compute("a\nb\n\nc\n".as_bytes(), &config).unwrap();
+        assert_eq!(stats.line_count, 2);
+        assert_eq!(stats.word_count, 3);
+    }
+    //
+    #[test]
+    fn test_empty_record_str() {
+        let r = StatsConfig::builder().record_str("").build();
+        assert!(r.is_err());
+    }
+    //
+    #[test]
+    fn test_invalid_utf8() {
+        let r = compute(&b"ab\xff\n"[..], &StatsConfig::default());
+        assert!(r.is_err());
+    }
+}
+
+mod test_6_counter_l {
+    use libaki_stats::{compute, Counter, Stats, StatsConfig};
+    //
+    const IN_DAT: &str = "abc def\r\n\n  \n\u{3042}\u{3044} \u{3046}\nghi \nlast";
+    //
+    fn expected() -> Stats {
+        compute(IN_DAT.as_bytes(), &StatsConfig::default()).unwrap()
+    }
+    //
+    #[test]
+    fn test_chunks() {
+        let bytes = IN_DAT.as_bytes();
+        for sz in 1..=bytes.len() {
+            let mut counter = Counter::default();
+            for chunk in bytes.chunks(sz) {
+                counter.update(chunk);
+            }
+            assert_eq!(counter.stats(), expected(), "chunk size: {sz}");
+        }
+    }
+    //
+    #[test]
+    fn test_merge() {
+        let bytes = IN_DAT.as_bytes();
+        for i in 0..=bytes.len() {
+            for j in i..=bytes.len() {
+                let mut a = Counter::default();
+                a.update(&bytes[..i]);
+                let mut b = Counter::default();
+                b.update(&bytes[i..j]);
+                let mut c = Counter::default();
+                c.update(&bytes[j..]);
+                b.merge(c);
+                a.merge(b);
+                assert_eq!(a.stats(), expected(), "split at: {i}, {j}");
+            }
+        }
+    }
+    //
+    #[test]
+    fn test_empty() {
+        let mut a = Counter::default();
+        a.merge(Counter::default());
+        assert_eq!(a.stats(), Stats::default());
+    }
+    //
+    #[test]
+    fn test_trailing_newline() {
+        let mut a = Counter::default();
+        a.update(b"a\n");
+        let mut b = Counter::default();
+        b.update(b"b\n");
+        a.merge(b);
+        assert_eq!(a.stats().line_count, 2);
+    }
+    //
+    #[test]
+    fn test_invalid_utf8() {
+        let mut a = Counter::default();
+        a.update(b"a\xff\n");
+        let stats = a.stats();
+        assert_eq!(stats.line_count, 1);
+        assert_eq!(stats.char_count, 2);
+    }
+    //
+    #[test]
+    fn test_word_delims() {
+        let config = StatsConfig::builder().word_delims(",").build().unwrap();
+        let mut a = Counter::new(config);
+        a.update(b"a,");
+        a.update(b"b\n");
+        assert_eq!(a.stats().word_count, 2);
+    }
+}
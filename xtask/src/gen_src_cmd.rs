@@ -10,13 +10,67 @@ pub fn do_gen_src() -> anyhow::Result<()> {
             let tup = match opt_str.lon_or_sho() {
                 "head" => (false, false, MetaType::Usize),
                 "tail" => (false, false, MetaType::Usize),
+                "code" => (true, false, MetaType::Other("opt_code_lang".into())),
+                "dup-approx" => (true, false, MetaType::Usize),
+                "dup-top" => (false, false, MetaType::Usize),
+                "emit-table" => (true, false, MetaType::Other("opt_table_lang".into())),
+                "exclude" => (false, true, opt_str.meta_type.clone()),
+                "files-from" => (true, false, opt_str.meta_type.clone()),
+                "files0-from" => (true, false, opt_str.meta_type.clone()),
+                "group-by" => (true, false, MetaType::Other("opt_group_by".into())),
+                "hash" => (true, false, MetaType::Other("opt_hash_alg".into())),
+                "include" => (false, true, opt_str.meta_type.clone()),
+                "locations" => (false, false, MetaType::Usize),
                 "locale" => (false, false, MetaType::Other("opt_locale_loc".into())),
+                "log-bucket" => (false, false, MetaType::Other("opt_log_bucket".into())),
+                "map-ascii" => (false, false, MetaType::Other("opt_map_ascii_mode".into())),
+                "map-sort" => (false, false, MetaType::Other("opt_map_sort".into())),
+                "map-width" => (true, false, MetaType::Usize),
+                "pattern" => (false, true, opt_str.meta_type.clone()),
+                "numeric-field" => (true, false, MetaType::Usize),
                 "query" => (true, false, opt_str.meta_type.clone()),
+                "record-sep" => (true, false, opt_str.meta_type.clone()),
+                "record-str" => (true, false, opt_str.meta_type.clone()),
+                "table-norm" => (false, false, MetaType::Other("opt_table_norm".into())),
+                "table-type" => (false, false, MetaType::Other("opt_table_type".into())),
+                "threads" => (false, false, MetaType::Usize),
                 //
                 "X" => (false, true, MetaType::Other("opt_uc_x_param".into())),
                 _ => return None,
             };
             Some(FixupType::from_tuple(tup))
         },
-    )
+    )?;
+    fixup_maybe_arg("src/conf/cmd.help.rs.txt", &[("map-ascii", "<mode>")])
+}
+
+// flood-tide-gen has no syntax for an optional argument,
+// so the options listed here are fixed up into `--name[=<meta>]`.
+fn fixup_maybe_arg(file_path: &str, opts: &[(&str, &str)]) -> anyhow::Result<()> {
+    let contents = std::fs::read_to_string(file_path)?;
+    let mut sss = contents.clone();
+    for (lon, meta) in opts {
+        let help_from = format!("--{lon} {meta}  ");
+        let help_to = format!("--{lon}[={meta}]");
+        sss = sss.replace(&help_from, &help_to);
+        //
+        let opt_key = format!("lon: \"{lon}\",");
+        sss = sss
+            .lines()
+            .map(|line| {
+                if line.contains(&opt_key) {
+                    line.replace("has: Arg::Yes,", "has: Arg::Maybe,")
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n";
+    }
+    if sss != contents {
+        println!("fixup: {file_path}");
+        std::fs::write(file_path, sss)?;
+    }
+    Ok(())
 }